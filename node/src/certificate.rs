@@ -0,0 +1,150 @@
+//! Verifiable course completion certificates.
+//!
+//! A certificate bundles the completion record of a learner together with a storage read proof
+//! taken at a finalized block. Anyone holding a trusted header for that block can check the
+//! certificate without trusting the node that produced it.
+
+use codec::{Decode, Encode};
+use node_kitties_runtime::{opaque::Header, pallet_courses::CourseCompletion, AccountId, Hash, Runtime};
+use serde::{Deserialize, Serialize};
+use sp_core::{
+	hashing::{twox_128, twox_64},
+	Bytes,
+};
+use sp_runtime::traits::{BlakeTwo256, Header as HeaderT};
+use sp_state_machine::{read_proof_check, StorageProof};
+
+/// Name of the courses pallet in `construct_runtime!`, used as storage prefix.
+const PALLET_PREFIX: &[u8] = b"Courses";
+/// Name of the storage item holding course completions.
+const STORAGE_PREFIX: &[u8] = b"CoursesCompleted";
+
+/// Completion certificate returned by the `courses_completionCertificate` RPC.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CompletionCertificate<BlockHash> {
+	/// Block whose state the proof was taken against.
+	pub block_hash: BlockHash,
+	/// SCALE encoded completion record, `None` if the course was not completed.
+	pub completion: Option<Bytes>,
+	/// Trie nodes proving the value of the completion record.
+	pub proof: Vec<Bytes>,
+}
+
+/// Errors raised while verifying a certificate.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Error {
+	/// The certificate was not issued against the given header.
+	HeaderMismatch,
+	/// The proof does not check against the header state root.
+	InvalidProof,
+	/// The completion record in the certificate differs from the proven value.
+	CompletionMismatch,
+	/// The proven value is not a valid completion record.
+	InvalidCompletion,
+}
+
+/// Storage key of the completion record of `learner` for `course_id`.
+///
+/// Both keys of `CoursesCompleted` use `Twox64Concat`, so the key can be rebuilt without
+/// querying the chain.
+pub fn completion_storage_key(learner: &AccountId, course_id: &Hash) -> Vec<u8> {
+	let mut key = twox_128(PALLET_PREFIX).to_vec();
+	key.extend_from_slice(&twox_128(STORAGE_PREFIX));
+	for encoded in [learner.encode(), course_id.encode()] {
+		key.extend_from_slice(&twox_64(&encoded));
+		key.extend_from_slice(&encoded);
+	}
+	key
+}
+
+/// Verify a certificate against a trusted `header`.
+///
+/// Returns the proven completion record, or `None` when the proof shows the learner had not
+/// completed the course at that block.
+pub fn verify_completion(
+	header: &Header,
+	learner: &AccountId,
+	course_id: &Hash,
+	certificate: &CompletionCertificate<Hash>,
+) -> Result<Option<CourseCompletion<Runtime>>, Error> {
+	if header.hash() != certificate.block_hash {
+		return Err(Error::HeaderMismatch)
+	}
+
+	let key = completion_storage_key(learner, course_id);
+	let proof = StorageProof::new(certificate.proof.iter().map(|node| node.to_vec()).collect());
+	let mut values = read_proof_check::<BlakeTwo256, _>(*header.state_root(), proof, &[&key])
+		.map_err(|_| Error::InvalidProof)?;
+	let proven = values.remove(&key).flatten();
+
+	if proven.as_deref() != certificate.completion.as_ref().map(|c| &c[..]) {
+		return Err(Error::CompletionMismatch)
+	}
+	proven
+		.map(|value| CourseCompletion::<Runtime>::decode(&mut &value[..]))
+		.transpose()
+		.map_err(|_| Error::InvalidCompletion)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use sp_state_machine::{prove_read, InMemoryBackend};
+	use std::collections::BTreeMap;
+
+	fn learner() -> AccountId {
+		AccountId::new([1; 32])
+	}
+
+	fn course_id() -> Hash {
+		Hash::repeat_byte(2)
+	}
+
+	fn completion() -> CourseCompletion<Runtime> {
+		CourseCompletion::<Runtime> { owner: learner(), completed_at: 7, edition: 0 }
+	}
+
+	// Builds a state holding the completion record, and a header and certificate for it.
+	fn certify() -> (Header, CompletionCertificate<Hash>) {
+		let key = completion_storage_key(&learner(), &course_id());
+		let mut storage = BTreeMap::new();
+		storage.insert(key.clone(), completion().encode());
+		storage.insert(b"unrelated".to_vec(), vec![0; 64]);
+		let backend = InMemoryBackend::<BlakeTwo256>::from(storage);
+		let state_root = *backend.root();
+		let proof = prove_read(backend, &[&key]).expect("key is in the trie");
+
+		let header = Header::new(1, Default::default(), state_root, Default::default(), Default::default());
+		let certificate = CompletionCertificate {
+			block_hash: header.hash(),
+			completion: Some(completion().encode().into()),
+			proof: proof.into_iter_nodes().map(Into::into).collect(),
+		};
+		(header, certificate)
+	}
+
+	#[test]
+	fn accepts_valid_certificate() {
+		let (header, certificate) = certify();
+		assert_eq!(verify_completion(&header, &learner(), &course_id(), &certificate), Ok(Some(completion())));
+	}
+
+	#[test]
+	fn rejects_altered_completion() {
+		let (header, mut certificate) = certify();
+		let forged = CourseCompletion::<Runtime> { completed_at: 1, ..completion() };
+		certificate.completion = Some(forged.encode().into());
+		assert_eq!(
+			verify_completion(&header, &learner(), &course_id(), &certificate),
+			Err(Error::CompletionMismatch)
+		);
+	}
+
+	#[test]
+	fn rejects_other_header() {
+		let (header, certificate) = certify();
+		let other = Header::new(2, Default::default(), *header.state_root(), header.hash(), Default::default());
+		assert_eq!(verify_completion(&other, &learner(), &course_id(), &certificate), Err(Error::HeaderMismatch));
+	}
+}
@@ -1,5 +1,6 @@
 use node_kitties_runtime::SubstrateKittiesConfig;
 use node_kitties_runtime::CoursesConfig;
+use node_kitties_runtime::pallet_courses::{GenesisClass, GenesisCourse, GenesisLecture, LectureKind, TokenType};
 use node_kitties_runtime::{
     AccountId, AuraConfig, BalancesConfig, GenesisConfig, GrandpaConfig, Signature, SudoConfig,
     SystemConfig, WASM_BINARY,
@@ -172,11 +173,47 @@ fn testnet_genesis(
         substrate_kitties: SubstrateKittiesConfig {
             kitties: vec![],
         },
-        courses: CoursesConfig {
-            courses: vec![],
-        },
+        courses: demo_courses(&endowed_accounts),
         treasury: Default::default(),
         council: Default::default(),
         technical_committee: Default::default(),
     }
 }
+
+/// Demo courses seeded on development chains: the first endowed account teaches, the others
+/// are enrolled and hold a badge of the course.
+fn demo_courses(endowed_accounts: &[AccountId]) -> CoursesConfig {
+    let (instructor, learners) = match endowed_accounts.split_first() {
+        Some((instructor, learners)) => (instructor.clone(), learners.to_vec()),
+        None => return CoursesConfig { courses: vec![], classes: vec![] },
+    };
+    let lecture = |name: &str, contents: &str, kind| GenesisLecture {
+        name: name.as_bytes().to_vec(),
+        contents: contents.as_bytes().to_vec(),
+        kind,
+    };
+
+    CoursesConfig {
+        courses: vec![GenesisCourse {
+            owner: instructor.clone(),
+            name: b"Introduction to Substrate".to_vec(),
+            category: b"Blockchain".to_vec(),
+            image_url: b"https://substrate.io/img/substrate_og.png".to_vec(),
+            description: b"Build your first blockchain with Substrate and FRAME.".to_vec(),
+            price: None,
+            // No assignments: the reviewer pool starts empty, so nobody could grade them.
+            lectures: vec![
+                lecture("What is Substrate", "https://docs.substrate.io/v3/getting-started/overview", LectureKind::Standard),
+                lecture("FRAME pallets", "https://docs.substrate.io/v3/runtime/frame", LectureKind::Standard),
+                lecture("Write your own pallet", "https://docs.substrate.io/tutorials/v3/proof-of-existence", LectureKind::Standard),
+            ],
+            live: true,
+            enrollments: learners.clone(),
+        }],
+        classes: vec![GenesisClass {
+            owner: instructor,
+            token_type: TokenType::BoundToAddress,
+            tokens: learners,
+        }],
+    }
+}
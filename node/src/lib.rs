@@ -1,3 +1,4 @@
+pub mod certificate;
 pub mod chain_spec;
 pub mod rpc;
 pub mod service;
@@ -1,12 +1,10 @@
 //! Substrate Node Template CLI library.
 #![warn(missing_docs)]
 
-mod chain_spec;
-#[macro_use]
-mod service;
 mod cli;
 mod command;
-mod rpc;
+
+use node_kitties::{chain_spec, service};
 
 fn main() -> sc_cli::Result<()> {
 	command::run()
@@ -7,12 +7,187 @@
 
 use std::sync::Arc;
 
-use node_kitties_runtime::{opaque::Block, AccountId, Balance, Index};
+use codec::Encode;
+use jsonrpc_core::{Error as RpcError, ErrorCode, Result as RpcResult};
+use jsonrpc_derive::rpc;
+use node_kitties_runtime::{
+	opaque::Block,
+	pallet_courses::{CourseAnalytics, CourseCompletion, Lecture, LocalizedText},
+	AccountId, Balance, Hash, Index, Runtime,
+};
+use pallet_courses_runtime_api::CoursesApi as CoursesRuntimeApi;
+use sc_client_api::ProofProvider;
 pub use sc_rpc_api::DenyUnsafe;
 use sc_transaction_pool_api::TransactionPool;
 use sp_api::ProvideRuntimeApi;
 use sp_block_builder::BlockBuilder;
 use sp_blockchain::{Error as BlockChainError, HeaderBackend, HeaderMetadata};
+use sp_runtime::generic::BlockId;
+
+use crate::certificate::{completion_storage_key, CompletionCertificate};
+
+/// Courses RPC methods.
+#[rpc]
+pub trait CoursesApi<BlockHash> {
+	/// Completion record of `learner` for `course_id`, with a storage read proof against
+	/// `at`, or the last finalized block when omitted. `at` must be a finalized block.
+	#[rpc(name = "courses_completionCertificate")]
+	fn completion_certificate(
+		&self,
+		learner: AccountId,
+		course_id: Hash,
+		at: Option<BlockHash>,
+	) -> RpcResult<CompletionCertificate<BlockHash>>;
+
+	/// Enrollment, completion funnel and revenue counters of `course_id` at `at`, or the best
+	/// block when omitted.
+	#[rpc(name = "courses_analytics")]
+	fn analytics(
+		&self,
+		course_id: Hash,
+		at: Option<BlockHash>,
+	) -> RpcResult<CourseAnalytics<Hash, u32, Balance>>;
+
+	/// Name and description of `course_id` in the preferred `language` (e.g. `pt-BR`), falling
+	/// back to its primary subtag and then to the default metadata.
+	#[rpc(name = "courses_localizedCourse")]
+	fn localized_course(
+		&self,
+		course_id: Hash,
+		language: String,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<LocalizedText>>;
+
+	/// Lectures of `course_id` released for `learner`, in the preferred `language` when
+	/// translated.
+	#[rpc(name = "courses_localizedLectures")]
+	fn localized_lectures(
+		&self,
+		course_id: Hash,
+		language: String,
+		learner: Option<AccountId>,
+		at: Option<BlockHash>,
+	) -> RpcResult<Vec<(Hash, LocalizedText)>>;
+}
+
+/// Implementation of the courses RPC methods.
+pub struct Courses<C> {
+	client: Arc<C>,
+}
+
+impl<C> Courses<C> {
+	/// Create new `Courses` with the given reference to the client.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client }
+	}
+}
+
+fn runtime_error(message: &str, err: impl std::fmt::Debug) -> RpcError {
+	RpcError {
+		code: ErrorCode::ServerError(1),
+		message: message.into(),
+		data: Some(format!("{:?}", err).into()),
+	}
+}
+
+impl<C> CoursesApi<Hash> for Courses<C>
+where
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + ProofProvider<Block>,
+	C: Send + Sync + 'static,
+	C::Api: CoursesRuntimeApi<
+		Block,
+		AccountId,
+		Hash,
+		CourseCompletion<Runtime>,
+		Lecture<Runtime>,
+		CourseAnalytics<Hash, u32, Balance>,
+		LocalizedText,
+	>,
+{
+	fn completion_certificate(
+		&self,
+		learner: AccountId,
+		course_id: Hash,
+		at: Option<Hash>,
+	) -> RpcResult<CompletionCertificate<Hash>> {
+		let info = self.client.info();
+		let block_hash = at.unwrap_or(info.finalized_hash);
+		// Certificates must not be backed by blocks that can still be reverted.
+		let number = self
+			.client
+			.number(block_hash)
+			.map_err(|e| runtime_error("Unable to query block number.", e))?
+			.ok_or_else(|| runtime_error("Unknown block.", block_hash))?;
+		let canonical = self
+			.client
+			.hash(number)
+			.map_err(|e| runtime_error("Unable to query block hash.", e))?;
+		if number > info.finalized_number || canonical != Some(block_hash) {
+			return Err(runtime_error("Block is not finalized.", block_hash));
+		}
+		let at = BlockId::hash(block_hash);
+
+		let completion = self
+			.client
+			.runtime_api()
+			.course_completion(&at, learner.clone(), course_id)
+			.map_err(|e| runtime_error("Unable to query course completion.", e))?;
+
+		let key = completion_storage_key(&learner, &course_id);
+		let proof = self
+			.client
+			.read_proof(&at, &mut std::iter::once(key.as_slice()))
+			.map_err(|e| runtime_error("Unable to generate storage proof.", e))?;
+
+		Ok(CompletionCertificate {
+			block_hash,
+			completion: completion.map(|c| c.encode().into()),
+			proof: proof.into_iter_nodes().map(Into::into).collect(),
+		})
+	}
+
+	fn analytics(
+		&self,
+		course_id: Hash,
+		at: Option<Hash>,
+	) -> RpcResult<CourseAnalytics<Hash, u32, Balance>> {
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		self.client
+			.runtime_api()
+			.course_analytics(&at, course_id)
+			.map_err(|e| runtime_error("Unable to query course analytics.", e))
+	}
+
+	fn localized_course(
+		&self,
+		course_id: Hash,
+		language: String,
+		at: Option<Hash>,
+	) -> RpcResult<Option<LocalizedText>> {
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		self.client
+			.runtime_api()
+			.localized_course(&at, course_id, language.into_bytes())
+			.map_err(|e| runtime_error("Unable to query localized course.", e))
+	}
+
+	fn localized_lectures(
+		&self,
+		course_id: Hash,
+		language: String,
+		learner: Option<AccountId>,
+		at: Option<Hash>,
+	) -> RpcResult<Vec<(Hash, LocalizedText)>> {
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		self.client
+			.runtime_api()
+			.localized_lectures(&at, course_id, learner, language.into_bytes())
+			.map_err(|e| runtime_error("Unable to query localized lectures.", e))
+	}
+}
 
 /// Full client dependencies.
 pub struct FullDeps<C, P> {
@@ -29,9 +204,19 @@ pub fn create_full<C, P>(deps: FullDeps<C, P>) -> jsonrpc_core::IoHandler<sc_rpc
 where
 	C: ProvideRuntimeApi<Block>,
 	C: HeaderBackend<Block> + HeaderMetadata<Block, Error = BlockChainError> + 'static,
+	C: ProofProvider<Block>,
 	C: Send + Sync + 'static,
 	C::Api: substrate_frame_rpc_system::AccountNonceApi<Block, AccountId, Index>,
 	C::Api: pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>,
+	C::Api: CoursesRuntimeApi<
+		Block,
+		AccountId,
+		Hash,
+		CourseCompletion<Runtime>,
+		Lecture<Runtime>,
+		CourseAnalytics<Hash, u32, Balance>,
+		LocalizedText,
+	>,
 	C::Api: BlockBuilder<Block>,
 	P: TransactionPool + 'static,
 {
@@ -45,6 +230,8 @@ where
 
 	io.extend_with(TransactionPaymentApi::to_delegate(TransactionPayment::new(client.clone())));
 
+	io.extend_with(CoursesApi::to_delegate(Courses::new(client.clone())));
+
 	// Extend this RPC with a custom API by using the following syntax.
 	// `YourRpcStruct` should have a reference to a client, which is needed
 	// to call into the runtime.
@@ -0,0 +1,48 @@
+//! Runtime API definition for the courses pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use sp_std::vec::Vec;
+
+sp_api::decl_runtime_apis! {
+    pub trait CoursesApi<AccountId, Hash, CourseCompletion, Lecture, CourseAnalytics, LocalizedText> where
+        AccountId: Codec,
+        Hash: Codec,
+        CourseCompletion: Codec,
+        Lecture: Codec,
+        CourseAnalytics: Codec,
+        LocalizedText: Codec,
+    {
+        /// The completion record of `learner` for `course_id`, if the course was completed.
+        fn course_completion(learner: AccountId, course_id: Hash) -> Option<CourseCompletion>;
+
+        /// The lectures of `course_id` released for `learner`, or released for everyone if `None`.
+        fn lectures(course_id: Hash, learner: Option<AccountId>) -> Vec<(Hash, Lecture)>;
+
+        /// Whether `account` is a verified instructor.
+        fn is_verified_instructor(account: AccountId) -> bool;
+
+        /// Enrollment, completion funnel and revenue counters of `course_id`.
+        fn course_analytics(course_id: Hash) -> CourseAnalytics;
+
+        /// Name and description of `course_id` in `language`, falling back to the default.
+        fn localized_course(course_id: Hash, language: Vec<u8>) -> Option<LocalizedText>;
+
+        /// The lectures of `course_id` released for `learner`, in `language` when translated.
+        fn localized_lectures(
+            course_id: Hash,
+            learner: Option<AccountId>,
+            language: Vec<u8>,
+        ) -> Vec<(Hash, LocalizedText)>;
+
+        /// The current and longest daily activity streaks of `learner`.
+        fn streak(learner: AccountId) -> (u32, u32);
+
+        /// The fastest learners to complete `course_id`, with their time to completion.
+        fn speed_leaderboard(course_id: Hash) -> Vec<(AccountId, u64)>;
+
+        /// The learners of `course_id` with the highest assignment scores.
+        fn score_leaderboard(course_id: Hash) -> Vec<(AccountId, u64)>;
+    }
+}
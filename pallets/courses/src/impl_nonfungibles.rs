@@ -0,0 +1,122 @@
+//! Implementations of the `nonfungibles` traits for the course NFT layer.
+
+use super::*;
+use frame_support::{
+    dispatch::DispatchResult,
+    ensure,
+    traits::tokens::nonfungibles::*,
+};
+use sp_runtime::{traits::One, ArithmeticError};
+use sp_std::prelude::*;
+
+impl<T: Config> Inspect<T::AccountId> for Pallet<T> {
+    type InstanceId = TokenId;
+    type ClassId = ClassId;
+
+    fn owner(class: &Self::ClassId, instance: &Self::InstanceId) -> Option<T::AccountId> {
+        Tokens::<T>::get(class, instance).map(|token| token.owner)
+    }
+
+    fn class_owner(class: &Self::ClassId) -> Option<T::AccountId> {
+        Classes::<T>::get(class).map(|info| info.owner)
+    }
+
+    /// Only tokens of `Transferable` classes can change hands.
+    fn can_transfer(class: &Self::ClassId, instance: &Self::InstanceId) -> bool {
+        let transferable = Classes::<T>::get(class).map_or(false, |info| info.token_type == TokenType::Transferable);
+        transferable && Tokens::<T>::contains_key(class, instance)
+    }
+}
+
+impl<T: Config> InspectEnumerable<T::AccountId> for Pallet<T> {
+    fn classes() -> Box<dyn Iterator<Item = Self::ClassId>> {
+        Box::new(Classes::<T>::iter_keys())
+    }
+
+    fn instances(class: &Self::ClassId) -> Box<dyn Iterator<Item = Self::InstanceId>> {
+        Box::new(Tokens::<T>::iter_key_prefix(class))
+    }
+
+    fn owned(who: &T::AccountId) -> Box<dyn Iterator<Item = (Self::ClassId, Self::InstanceId)>> {
+        Box::new(TokensByOwner::<T>::iter_key_prefix((who.clone(),)))
+    }
+
+    fn owned_in_class(class: &Self::ClassId, who: &T::AccountId) -> Box<dyn Iterator<Item = Self::InstanceId>> {
+        Box::new(TokensByOwner::<T>::iter_key_prefix((who.clone(), *class)))
+    }
+}
+
+impl<T: Config> Create<T::AccountId> for Pallet<T> {
+    /// Create a `Transferable` class owned by `who`. Classes have a single owner, so `admin`
+    /// must be `who`.
+    fn create_class(class: &Self::ClassId, who: &T::AccountId, admin: &T::AccountId) -> DispatchResult {
+        ensure!(admin == who, Error::<T>::ClassAdminNotOwner);
+        ensure!(!Classes::<T>::contains_key(class), Error::<T>::ClassAlreadyExists);
+
+        // Keep automatically assigned class IDs clear of the ones created here.
+        NextClassId::<T>::try_mutate(|id| -> DispatchResult {
+            if *class >= *id {
+                *id = class.checked_add(One::one()).ok_or(Error::<T>::NoAvailableClassId)?;
+            }
+            Ok(())
+        })?;
+        let info = ClassInfo {
+            total_issuance: Default::default(),
+            owner: who.clone(),
+            token_type: TokenType::Transferable,
+        };
+        Classes::<T>::insert(class, info);
+        Ok(())
+    }
+}
+
+impl<T: Config> Mutate<T::AccountId> for Pallet<T> {
+    fn mint_into(class: &Self::ClassId, instance: &Self::InstanceId, who: &T::AccountId) -> DispatchResult {
+        ensure!(!Tokens::<T>::contains_key(class, instance), Error::<T>::TokenAlreadyExists);
+
+        Classes::<T>::try_mutate(class, |class_info| -> DispatchResult {
+            let info = class_info.as_mut().ok_or(Error::<T>::ClassNotFound)?;
+            info.total_issuance = info
+                .total_issuance
+                .checked_add(One::one())
+                .ok_or(ArithmeticError::Overflow)?;
+            Ok(())
+        })?;
+        // Keep automatically assigned token IDs clear of the ones minted here.
+        NextTokenId::<T>::try_mutate(class, |id| -> DispatchResult {
+            if *instance >= *id {
+                *id = instance.checked_add(One::one()).ok_or(Error::<T>::NoAvailableTokenId)?;
+            }
+            Ok(())
+        })?;
+        Tokens::<T>::insert(class, instance, TokenInfo { owner: who.clone() });
+        TokensByOwner::<T>::insert((who.clone(), *class, *instance), ());
+        Ok(())
+    }
+
+    /// Burn a token of a `Transferable` class. Certificates and badges are bound to their
+    /// holder and cannot be burned.
+    fn burn_from(class: &Self::ClassId, instance: &Self::InstanceId) -> DispatchResult {
+        let class_info = Classes::<T>::get(class).ok_or(Error::<T>::ClassNotFound)?;
+        ensure!(class_info.token_type == TokenType::Transferable, Error::<T>::TokenNotTransferable);
+        let token = Tokens::<T>::take(class, instance).ok_or(Error::<T>::TokenNotFound)?;
+        Classes::<T>::mutate(class, |class_info| {
+            if let Some(info) = class_info {
+                info.total_issuance = info.total_issuance.saturating_sub(One::one());
+            }
+        });
+        TokensByOwner::<T>::remove((token.owner, *class, *instance));
+        Listings::<T>::remove(class, instance);
+        Ok(())
+    }
+}
+
+impl<T: Config> Transfer<T::AccountId> for Pallet<T> {
+    fn transfer(class: &Self::ClassId, instance: &Self::InstanceId, destination: &T::AccountId) -> DispatchResult {
+        let class_info = Classes::<T>::get(class).ok_or(Error::<T>::ClassNotFound)?;
+        ensure!(class_info.token_type == TokenType::Transferable, Error::<T>::TokenNotTransferable);
+        let owner = Self::owner(class, instance).ok_or(Error::<T>::TokenNotFound)?;
+
+        Self::transfer_token_to(*class, *instance, &owner, destination)
+    }
+}
@@ -2,16 +2,29 @@
 
 pub use pallet::*;
 mod rng;
+mod impl_nonfungibles;
+pub mod migrations;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
 
 #[frame_support::pallet]
 pub mod pallet {
-    use sp_std::prelude::*;
+    use sp_std::{convert::TryInto, prelude::*};
     use frame_support::pallet_prelude::*;
     use frame_system::pallet_prelude::*;
     use frame_support::{
         sp_runtime::traits::Hash,
-        traits::{Randomness, Currency, tokens::ExistenceRequirement},
-        transactional,
+        traits::{
+            Randomness, Currency, ReservableCurrency, EnsureOrigin, Imbalance, OnUnbalanced, Time,
+            WithdrawReasons,
+            tokens::{fungibles, ExistenceRequirement},
+        },
+        storage::{with_transaction, TransactionOutcome},
+        transactional, PalletId,
     };
     use sp_io::hashing::blake2_128;
     use scale_info::TypeInfo;
@@ -20,7 +33,7 @@ pub mod pallet {
     use frame_system::RawOrigin;
     use sp_runtime::traits::{BlakeTwo256, Bounded};
     use sp_runtime::{
-        traits::{CheckedSub, AtLeast32BitUnsigned, SaturatedConversion, StaticLookup, One, Zero},
+        traits::{AccountIdConversion, CheckedSub, AtLeast32BitUnsigned, SaturatedConversion, Saturating, StaticLookup, One, Zero},
         DispatchError, Perbill, Percent, ArithmeticError,
     };
     use crate::rng::RandomNumberGenerator;
@@ -28,8 +41,17 @@ pub mod pallet {
     type AccountOf<T> = <T as frame_system::Config>::AccountId;
     type BalanceOf<T> =
     <<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
-    type ClassId = u32;
-    type TokenId = u64;
+    type NegativeImbalanceOf<T> =
+    <<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::NegativeImbalance;
+    type MomentOf<T> = <<T as Config>::Time as Time>::Moment;
+    pub type ClassId = u32;
+    pub type TokenId = u64;
+    type CohortId = u32;
+    type PathId = u64;
+    type EditionId = u32;
+    type SectionId = u32;
+    type BadgeRuleId = u32;
+    type LanguageTag<T> = BoundedVec<u8, <T as Config>::MaxLanguageTagLen>;
 
     // Struct for holding Course information.
     #[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo)]
@@ -40,7 +62,68 @@ pub mod pallet {
         pub image_url: Vec<u8>,
         pub category: Vec<u8>,
         pub description: Vec<u8>,
-        pub live: bool
+        pub live: bool,
+        pub price: Option<BalanceOf<T>>,
+        pub currency: PaymentCurrency<T::AssetId>,
+        pub license: License,
+        /// The course this one was forked from.
+        pub parent: Option<T::Hash>,
+        /// Share of sales owed to the parent owner, fixed by the parent license at fork time.
+        pub parent_royalty: Option<Perbill>,
+    }
+
+    /// License a course is published under.
+    #[derive(Encode, Decode, Copy, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+    pub enum License {
+        /// The course cannot be forked.
+        AllRightsReserved,
+        /// The course can be forked with attribution.
+        CcBy,
+        /// The course can be forked with attribution, derivatives keep the same license.
+        CcBySa,
+        /// The course can be forked, the parent owner earns the given share of derivative sales.
+        CommercialWithRoyalty(Perbill),
+    }
+
+    impl Default for License {
+        fn default() -> Self {
+            License::AllRightsReserved
+        }
+    }
+
+    /// A translation of the name and description of a Course, or of the name and contents of a
+    /// Lecture.
+    #[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+    pub struct Translation {
+        pub name: Vec<u8>,
+        pub body: Vec<u8>,
+    }
+
+    /// Metadata of a Course or Lecture in the language served to the caller of the runtime API.
+    #[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+    pub struct LocalizedText {
+        /// Language of the translation served, `None` for the default metadata.
+        pub language: Option<Vec<u8>>,
+        pub name: Vec<u8>,
+        /// The course description, or the lecture contents.
+        pub body: Vec<u8>,
+    }
+
+    /// Currency a course is priced in.
+    #[derive(Encode, Decode, Copy, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+    pub enum PaymentCurrency<AssetId> {
+        /// The native token handled by `T::Currency`.
+        Native,
+        /// An asset handled by `T::Assets`.
+        Asset(AssetId),
+    }
+
+    impl<AssetId> Default for PaymentCurrency<AssetId> {
+        fn default() -> Self {
+            PaymentCurrency::Native
+        }
     }
 
 
@@ -51,9 +134,29 @@ pub mod pallet {
         pub total_issuance: TokenId,
         /// Class owner
         pub owner: AccountId,
+        /// Whether the tokens of the class can change hands
+        pub token_type: TokenType,
     }
 
-    #[derive(Encode, Decode, Copy, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+    /// Royalty paid on secondary sales of the tokens of a class.
+    #[derive(Encode, Decode, Clone, Eq, PartialEq, MaxEncodedLen, RuntimeDebug, TypeInfo)]
+    pub struct ClassRoyalty<AccountId> {
+        /// Share of the sale price paid as royalty
+        pub rate: Perbill,
+        /// Account receiving the royalty
+        pub beneficiary: AccountId,
+    }
+
+    // Struct for holding Listing information.
+    #[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo)]
+    #[scale_info(skip_type_params(T))]
+    pub struct Listing<T: Config> {
+        pub price: BalanceOf<T>,
+        /// Class royalty at listing time, later changes do not apply to the sale.
+        pub royalty: Option<ClassRoyalty<T::AccountId>>,
+    }
+
+    #[derive(Encode, Decode, Copy, Clone, PartialEq, Eq, MaxEncodedLen, RuntimeDebug, TypeInfo)]
     #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
     pub enum TokenType {
         Transferable,
@@ -89,6 +192,15 @@ pub mod pallet {
     }
 
 
+    #[derive(Encode, Decode, Copy, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+    pub enum LectureKind {
+        /// Completed directly by the learner.
+        Standard,
+        /// Project-based lecture graded by the given number of peer reviewers.
+        Assignment { reviewers: u8 },
+    }
+
     // Struct for holding Lecture information.
     #[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo)]
     #[scale_info(skip_type_params(T))]
@@ -96,6 +208,46 @@ pub mod pallet {
         pub name: Vec<u8>,
         pub contents: Vec<u8>,
         pub owner: AccountOf<T>,
+        pub kind: LectureKind,
+        /// When the lecture unlocks, released right away if `None`.
+        pub release: Option<ReleaseSchedule<T::BlockNumber>>,
+    }
+
+    #[derive(Encode, Decode, Copy, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+    pub enum ReleaseSchedule<BlockNumber> {
+        /// Released for everyone at the given block.
+        At(BlockNumber),
+        /// Released the given number of blocks after the learner enrolled.
+        AfterEnrollment(BlockNumber),
+    }
+
+    // Struct for holding WrappedKey information.
+    #[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo)]
+    #[scale_info(skip_type_params(T))]
+    pub struct WrappedKey<T: Config> {
+        /// The content key of the lecture, encrypted to the public key of the learner.
+        pub key: BoundedVec<u8, T::MaxWrappedKeyLen>,
+        pub granted_at: T::BlockNumber,
+    }
+
+    // Struct for holding Submission information.
+    #[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo)]
+    #[scale_info(skip_type_params(T))]
+    pub struct Submission<T: Config> {
+        /// Hash of the submitted work.
+        pub content: T::Hash,
+        pub reviewers: BoundedVec<AccountOf<T>, T::MaxReviewers>,
+        pub scores: BoundedVec<(AccountOf<T>, u8), T::MaxReviewers>,
+        pub submitted_at: T::BlockNumber,
+    }
+
+    /// Reviewer reputation, built from how often a reviewer agrees with the final median score.
+    #[derive(Encode, Decode, Clone, Default, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+    pub struct Reputation {
+        /// Number of reviews submitted.
+        pub reviews: u32,
+        /// Number of reviews within `T::ReviewTolerance` of the median score.
+        pub agreements: u32,
     }
 
     // Struct for holding LectureCompleted information.
@@ -103,10 +255,254 @@ pub mod pallet {
     #[scale_info(skip_type_params(T))]
     pub struct LectureCompleted<T: Config> {
         pub owner: AccountOf<T>,
+        /// Edition of the course the lecture was studied in.
+        pub edition: EditionId,
+    }
+
+    // Struct for holding CourseCompletion information.
+    #[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo)]
+    #[scale_info(skip_type_params(T))]
+    pub struct CourseCompletion<T: Config> {
+        pub owner: AccountOf<T>,
+        pub completed_at: T::BlockNumber,
+        /// Edition of the course that was completed.
+        pub edition: EditionId,
+    }
+
+    /// A lecture to add in bulk, with `create_course_with_lectures` or `import_lectures`.
+    #[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+    pub struct LectureInput {
+        pub name: Vec<u8>,
+        pub contents: Vec<u8>,
+        pub kind: LectureKind,
+    }
+
+    /// A named group of lectures to add in bulk.
+    #[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+    pub struct SectionInput {
+        pub name: Vec<u8>,
+        pub lectures: Vec<LectureInput>,
+    }
+
+    // Struct for holding Section information.
+    #[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo)]
+    #[scale_info(skip_type_params(T))]
+    pub struct Section<T: Config> {
+        pub name: Vec<u8>,
+        /// Lectures of the section, in order.
+        pub lectures: BoundedVec<T::Hash, T::MaxBulkLectures>,
+    }
+
+    // Struct for holding Edition information.
+    #[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo)]
+    #[scale_info(skip_type_params(T))]
+    pub struct Edition<T: Config> {
+        /// Snapshot of the lecture set, as `(lecture_id, content_hash)` pairs.
+        pub lectures: BoundedVec<(T::Hash, T::Hash), T::MaxEditionLectures>,
+        pub published_at: T::BlockNumber,
+    }
+
+    /// Condition a learner must meet to earn a badge.
+    #[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+    pub enum BadgeCondition {
+        /// Complete the given number of lectures, in the rule course or overall.
+        LecturesCompleted(u32),
+        /// Complete a course within the given time of starting it, in `T::Time` units.
+        CourseWithin(u64),
+        /// Complete the given number of courses of a category.
+        CategoryCourses { category: Vec<u8>, count: u32 },
+        /// Study on the given number of consecutive days.
+        Streak(u32),
+    }
+
+    // Struct for holding BadgeRule information.
+    #[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo)]
+    #[scale_info(skip_type_params(T))]
+    pub struct BadgeRule<T: Config> {
+        pub name: Vec<u8>,
+        /// Course the rule is limited to, `None` for rules defined by governance.
+        pub course: Option<T::Hash>,
+        pub condition: BadgeCondition,
+        /// Class of the badge tokens, bound to the learners who earn them.
+        pub class_id: ClassId,
+    }
+
+    /// Daily activity of a learner, in `T::DayLength` buckets.
+    #[derive(Encode, Decode, Clone, Default, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+    pub struct Streak {
+        /// Last day with a completed lecture.
+        pub last_day: u64,
+        /// Number of consecutive days with a completed lecture, up to `last_day`.
+        pub current: u32,
+    }
+
+    /// Ranking criteria of the course leaderboards.
+    #[derive(Encode, Decode, Copy, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+    pub enum LeaderboardKind {
+        /// Time from the first completed lecture to the course completion, lowest first.
+        Speed,
+        /// Sum of the median scores of passed assignments, highest first.
+        Score,
+    }
+
+    /// Learn-to-earn reward plan attached to a course.
+    ///
+    /// Rewards are paid out of the course reward pot (see `Pallet::reward_pot`), which is
+    /// usually funded through a `pallet_treasury` spend proposal naming the pot as beneficiary.
+    /// Only completions attested by a third party are rewarded: lectures of learners who paid
+    /// for their enrollment, and peer-graded assignments.
+    #[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo)]
+    #[scale_info(skip_type_params(T))]
+    pub struct RewardPlan<T: Config> {
+        /// Amount paid for every verified lecture completion.
+        pub per_lecture: BalanceOf<T>,
+        /// Amount paid once the learner completes every lecture of the course.
+        pub completion_bonus: BalanceOf<T>,
+        /// Maximum amount a single learner can earn from this course.
+        pub learner_cap: BalanceOf<T>,
+    }
+
+    // Struct for holding Complaint information.
+    #[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo)]
+    #[scale_info(skip_type_params(T))]
+    pub struct Complaint<T: Config> {
+        pub reporter: AccountOf<T>,
+        /// Hash of the off-chain evidence backing the complaint.
+        pub evidence: T::Hash,
+        pub filed_at: T::BlockNumber,
+        /// Deposit reserved from the reporter, slashed if the complaint is dismissed.
+        pub deposit: BalanceOf<T>,
+    }
+
+    // Struct for holding Enrollment information.
+    #[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo)]
+    #[scale_info(skip_type_params(T))]
+    pub struct Enrollment<T: Config> {
+        pub enrolled_at: T::BlockNumber,
+        pub paid: BalanceOf<T>,
+    }
+
+    /// Analytics of a Course, returned by the runtime API.
+    #[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+    #[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+    pub struct CourseAnalytics<Hash, AssetId, Balance> {
+        /// Number of enrollments.
+        pub enrollments: u32,
+        /// Number of completions of each lecture, in the order of the current edition.
+        pub lecture_completions: Vec<(Hash, u32)>,
+        /// Number of learners who completed the course.
+        pub completions: u32,
+        /// Gross revenue of the course, per payment currency.
+        pub revenue: Vec<(PaymentCurrency<AssetId>, Balance)>,
+    }
+
+    // Struct for holding EnrollmentRules information.
+    #[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo)]
+    #[scale_info(skip_type_params(T))]
+    pub struct EnrollmentRules<T: Config> {
+        /// Courses a learner must have completed before enrolling.
+        pub prerequisites: BoundedVec<T::Hash, T::MaxPrerequisites>,
+        /// Maximum amount of enrollments, unlimited if `None`.
+        pub capacity: Option<u32>,
+    }
+
+    /// Referral statistics of an affiliate, per payment currency.
+    #[derive(Encode, Decode, Clone, Default, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+    pub struct ReferralStats<Balance> {
+        /// Number of enrollments referred.
+        pub referrals: u32,
+        /// Total commission earned.
+        pub earned: Balance,
+    }
+
+    // Struct for holding Gift information.
+    #[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo)]
+    #[scale_info(skip_type_params(T))]
+    pub struct Gift<T: Config> {
+        pub payer: AccountOf<T>,
+        /// Amount held in escrow until the gift is accepted or cancelled.
+        pub amount: BalanceOf<T>,
+        pub currency: PaymentCurrency<T::AssetId>,
+        pub offered_at: T::BlockNumber,
+        /// Coupon redeemed by the payer, handed back if the gift is cancelled.
+        pub coupon: Option<T::Hash>,
+    }
+
+    // Struct for holding LearningPath information.
+    #[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo)]
+    #[scale_info(skip_type_params(T))]
+    pub struct LearningPath<T: Config> {
+        pub name: Vec<u8>,
+        pub owner: AccountOf<T>,
+        /// Ordered list of the Courses making up the path.
+        pub courses: BoundedVec<T::Hash, T::MaxPathCourses>,
+        /// Discount applied to the course prices when enrolling in the whole path.
+        pub discount: Percent,
+        /// NFT class of the certificates issued on path completion.
+        pub certificate_class: ClassId,
+    }
+
+    #[derive(Encode, Decode, Copy, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+    pub enum Discount<Balance> {
+        /// Percentage taken off the course price.
+        Percentage(Percent),
+        /// Fixed amount taken off the course price.
+        Fixed(Balance),
+    }
+
+    // Struct for holding Coupon information.
+    #[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo)]
+    #[scale_info(skip_type_params(T))]
+    pub struct Coupon<T: Config> {
+        pub owner: AccountOf<T>,
+        pub discount: Discount<BalanceOf<T>>,
+        pub max_redemptions: u32,
+        pub redemptions: u32,
+        /// Last block at which the coupon can be redeemed.
+        pub expires_at: T::BlockNumber,
+        /// Course the coupon is restricted to, any course of the owner otherwise.
+        pub course: Option<T::Hash>,
+    }
+
+    // Struct for holding Cohort information.
+    #[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo)]
+    #[scale_info(skip_type_params(T))]
+    pub struct Cohort<T: Config> {
+        pub start: MomentOf<T>,
+        pub end: MomentOf<T>,
+        pub max_seats: u32,
+        pub seats_taken: u32,
+        pub closed: bool,
+    }
+
+    // Struct for holding CohortCompletion information.
+    #[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo)]
+    #[scale_info(skip_type_params(T))]
+    pub struct CohortCompletion<T: Config> {
+        pub completed_at: MomentOf<T>,
+        /// Whether the lecture was completed after its due date.
+        pub late: bool,
+    }
+
+    /// Source of the verified instructor flag.
+    pub trait InstructorVerifier<AccountId> {
+        /// Whether `who` is a verified instructor.
+        fn is_verified(who: &AccountId) -> bool;
+    }
+
+    impl<AccountId> InstructorVerifier<AccountId> for () {
+        fn is_verified(_who: &AccountId) -> bool {
+            false
+        }
     }
 
+    /// The current storage version.
+    const STORAGE_VERSION: StorageVersion = StorageVersion::new(3);
+
     #[pallet::pallet]
     #[pallet::generate_store(trait Store)]
+    #[pallet::storage_version(STORAGE_VERSION)]
     pub struct Pallet<T>(_);
 
     // Configure the pallet by specifying the parameters and types on which it depends.
@@ -116,7 +512,7 @@ pub mod pallet {
         type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
 
         /// The Currency handler for the Courses pallet.
-        type Currency: Currency<Self::AccountId>;
+        type Currency: ReservableCurrency<Self::AccountId>;
 
         /// The maximum amount of Courses a single account can own.
         #[pallet::constant]
@@ -125,10 +521,129 @@ pub mod pallet {
         /// The type of Randomness we want to specify for this pallet.
         type CourseRandomness: Randomness<Self::Hash, Self::BlockNumber>;
 
+        /// The Courses pallet id, used for deriving the reward pot of every course.
+        #[pallet::constant]
+        type PalletId: Get<PalletId>;
+
+        /// Origin, besides the course owner, allowed to manage course reward plans.
+        type RewardOrigin: EnsureOrigin<Self::Origin>;
+
+        /// The bond an instructor reserves when publishing a course.
+        #[pallet::constant]
+        type CourseBond: Get<BalanceOf<Self>>;
+
+        /// Origin allowed to dismiss complaints and take abusive courses down.
+        type TakedownOrigin: EnsureOrigin<Self::Origin>;
+
+        /// The deposit a reporter reserves when filing a complaint.
+        #[pallet::constant]
+        type ComplaintDeposit: Get<BalanceOf<Self>>;
+
+        /// The maximum amount of open complaints against a course.
+        #[pallet::constant]
+        type MaxComplaints: Get<u32>;
+
+        /// The number of blocks after which a complaint nobody acted upon can be expired.
+        #[pallet::constant]
+        type ComplaintPeriod: Get<Self::BlockNumber>;
+
+        /// Handler for the bonds and deposits slashed when a course is taken down or a complaint
+        /// is dismissed.
+        type Slashed: OnUnbalanced<NegativeImbalanceOf<Self>>;
+
+        /// Time provider for cohort schedules, usually `pallet_timestamp`.
+        type Time: Time;
+
+        /// The maximum amount of peer reviewers assigned to a submission.
+        #[pallet::constant]
+        type MaxReviewers: Get<u32>;
+
+        /// The maximum amount of accounts in the reviewer pool of a course.
+        #[pallet::constant]
+        type MaxReviewerPool: Get<u32>;
+
+        /// The minimum median score, out of 100, for a submission to pass.
+        #[pallet::constant]
+        type PassingScore: Get<u8>;
+
+        /// The maximum distance to the median score for a review to count as an agreement.
+        #[pallet::constant]
+        type ReviewTolerance: Get<u8>;
+
+        /// The number of blocks reviewers have to score a submission before the learner can
+        /// reassign it or withdraw it.
+        #[pallet::constant]
+        type ReviewPeriod: Get<Self::BlockNumber>;
+
+        /// The maximum amount of Courses in a learning path.
+        #[pallet::constant]
+        type MaxPathCourses: Get<u32>;
+
+        /// The maximum amount of learning paths an account can enroll in.
+        #[pallet::constant]
+        type MaxLearnerPaths: Get<u32>;
+
+        /// The maximum amount of redemptions of a coupon.
+        #[pallet::constant]
+        type MaxCouponRedemptions: Get<u32>;
+
+        /// The maximum amount of prerequisites of a Course.
+        #[pallet::constant]
+        type MaxPrerequisites: Get<u32>;
+
+        /// The maximum amount of Lectures in a course edition.
+        #[pallet::constant]
+        type MaxEditionLectures: Get<u32>;
+
+        /// The maximum length of a content key wrapped for a learner.
+        #[pallet::constant]
+        type MaxWrappedKeyLen: Get<u32>;
+
+        /// The maximum amount of Lectures added in a single bulk call, also bounding sections.
+        #[pallet::constant]
+        type MaxBulkLectures: Get<u32>;
+
+        /// The maximum length of a language tag, e.g. `pt-BR`.
+        #[pallet::constant]
+        type MaxLanguageTagLen: Get<u32>;
+
+        /// The maximum amount of languages a course, or one of its lectures, is translated into.
+        #[pallet::constant]
+        type MaxTranslations: Get<u32>;
+
+        /// The maximum amount of active badge rules of a course, and of global badge rules.
+        #[pallet::constant]
+        type MaxBadgeRules: Get<u32>;
+
+        /// Length of a day in `Time` units, bucketing learner activity for streaks.
+        #[pallet::constant]
+        type DayLength: Get<MomentOf<Self>>;
+
+        /// The maximum amount of learners ranked on a course leaderboard.
+        #[pallet::constant]
+        type MaxLeaderboardSize: Get<u32>;
+
+        /// Tells which accounts are verified instructors.
+        type InstructorVerifier: InstructorVerifier<Self::AccountId>;
+
+        /// Whether only verified instructors can publish courses.
+        #[pallet::constant]
+        type RequireVerifiedInstructor: Get<bool>;
+
+        /// Identifier of the assets Courses can be priced in.
+        type AssetId: Member + Parameter + Copy;
+
+        /// The fungible assets handler Courses can be priced in, usually `pallet_assets`.
+        type Assets: fungibles::Transfer<Self::AccountId, AssetId = Self::AssetId, Balance = BalanceOf<Self>>;
+
         /// The maximum size of a class's metadata
         type MaxClassMetadata: Get<u32>;
         /// The maximum size of a token's metadata
         type MaxTokenMetadata: Get<u32>;
+
+        /// The maximum royalty a class can charge on secondary sales of its tokens.
+        #[pallet::constant]
+        type MaxClassRoyalty: Get<Perbill>;
     }
 
     pub type ClassMetadataOf<T> = BoundedVec<u8, <T as Config>::MaxClassMetadata>;
@@ -150,7 +665,7 @@ pub mod pallet {
         ExceedMaxCourseOwned,
         /// Buyer cannot be the owner.
         BuyerIsCourseOwner,
-        /// Cannot transfer a course to its owner.
+        /// Cannot transfer a course or token to its owner.
         TransferToSelf,
         /// Handles checking whether the Course exists.
         CourseNotExist,
@@ -168,6 +683,210 @@ pub mod pallet {
         NoAvailableTokenId,
         /// Class not found
         ClassNotFound,
+        /// Lectures can only be completed on published courses.
+        CourseNotPublished,
+        /// The lecture has already been completed by this account.
+        LectureAlreadyCompleted,
+        /// The course has no reward plan attached.
+        RewardPlanNotExist,
+        /// The reward pot cannot be swept while a reward plan is attached.
+        RewardPlanActive,
+        /// Only `T::RewardOrigin` can raise the rates of a funded reward plan.
+        RewardPlanLocked,
+        /// The course has been taken down and cannot be published again.
+        CourseTakenDown,
+        /// Handles checking that the Course is published.
+        CourseNotLive,
+        /// Handles checking whether the Complaint exists.
+        ComplaintNotExist,
+        /// The course cannot be unpublished while complaints against it are open.
+        ComplaintsPending,
+        /// Handles arithmetic overflow when incrementing the Complaint counter.
+        ComplaintCntOverflow,
+        /// A course cannot have more open complaints than `MaxComplaints`.
+        TooManyComplaints,
+        /// The complaint period has not elapsed yet.
+        ComplaintPeriodNotElapsed,
+        /// Handles checking whether the Cohort exists.
+        CohortNotExist,
+        /// Handles arithmetic overflow when incrementing the Cohort counter.
+        CohortCntOverflow,
+        /// A cohort must end after it starts.
+        InvalidCohortSchedule,
+        /// A cohort cannot start in the past.
+        CohortStartInPast,
+        /// A cohort must offer at least one seat.
+        NoCohortSeats,
+        /// The cohort has been closed.
+        CohortClosed,
+        /// The cohort has already started and no longer takes new members.
+        CohortStarted,
+        /// The cohort has not ended yet.
+        CohortNotEnded,
+        /// Every seat of the cohort is taken.
+        CohortFull,
+        /// An account can only join one cohort per course.
+        AlreadyInCohort,
+        /// The account is not a member of a cohort of the course.
+        NotInCohort,
+        /// Assignments must be graded through peer review.
+        LectureRequiresReview,
+        /// The lecture is not an assignment.
+        NotAnAssignment,
+        /// The number of reviewers must be between one and `MaxReviewers`.
+        InvalidReviewerCount,
+        /// The reviewer pool does not have enough accounts to grade a submission.
+        NotEnoughReviewers,
+        /// The reviewer pool of the course is full.
+        ReviewerPoolFull,
+        /// The account is already in the reviewer pool.
+        AlreadyReviewer,
+        /// The account is not in the reviewer pool.
+        NotReviewer,
+        /// Reviewers must have completed the course or have been approved by its owner.
+        NotQualifiedReviewer,
+        /// The review period of the submission has not elapsed yet.
+        ReviewPeriodNotElapsed,
+        /// A submission for this assignment is already awaiting review.
+        SubmissionPending,
+        /// The assignment was passed already, `complete_lecture` records the completion.
+        CompletionPending,
+        /// Handles checking whether the Submission exists.
+        SubmissionNotExist,
+        /// The account is not assigned to review this submission.
+        NotAssignedReviewer,
+        /// The reviewer already scored this submission.
+        AlreadyReviewed,
+        /// Scores must be between 0 and 100.
+        InvalidScore,
+        /// The account is already enrolled in the course.
+        AlreadyEnrolled,
+        /// Paid courses can only be followed by enrolled accounts.
+        NotEnrolled,
+        /// The asset a course is priced in does not exist.
+        AssetNotExist,
+        /// Every paid course of a learning path must be priced in the same currency.
+        MixedCurrencyPath,
+        /// Handles checking whether the LearningPath exists.
+        LearningPathNotExist,
+        /// Handles arithmetic overflow when incrementing the LearningPath counter.
+        LearningPathCntOverflow,
+        /// A learning path must contain between one and `MaxPathCourses` courses.
+        InvalidPathCourses,
+        /// A course appears more than once in the learning path.
+        DuplicatePathCourse,
+        /// The account is already enrolled in the learning path.
+        AlreadyEnrolledInPath,
+        /// An account cannot enroll in more than `MaxLearnerPaths` learning paths.
+        TooManyPaths,
+        /// A coupon with the same code already exists.
+        CouponAlreadyExists,
+        /// Handles checking whether the Coupon exists.
+        CouponNotExist,
+        /// Handles checking that the Coupon is owned by the account.
+        NotCouponOwner,
+        /// The coupon has expired.
+        CouponExpired,
+        /// The coupon reached its redemption limit.
+        CouponExhausted,
+        /// The coupon does not apply to this course.
+        CouponNotApplicable,
+        /// The account already redeemed this coupon.
+        CouponAlreadyRedeemed,
+        /// A coupon must allow between one and `MaxCouponRedemptions` redemptions.
+        InvalidRedemptionLimit,
+        /// The learner has not completed every prerequisite of the course.
+        PrerequisitesNotMet,
+        /// The course reached its enrollment capacity.
+        CourseFull,
+        /// A course cannot have more than `MaxPrerequisites` prerequisites, nor depend on itself.
+        InvalidPrerequisites,
+        /// Gifts are for other accounts, use `enroll` instead.
+        GiftToSelf,
+        /// A gift for this course is already awaiting the recipient.
+        GiftAlreadyPending,
+        /// Handles checking whether the Gift exists.
+        GiftNotExist,
+        /// Only the payer or the recipient can cancel a gift.
+        NotGiftParty,
+        /// The lecture is not released yet for this account.
+        LectureNotReleased,
+        /// The course has more lectures than an edition can hold.
+        TooManyLectures,
+        /// A bulk import cannot create more sections than `MaxBulkLectures`.
+        TooManySections,
+        /// Handles arithmetic overflow when incrementing the Edition counter.
+        EditionCntOverflow,
+        /// The lecture is not part of the edition the learner studies.
+        LectureNotInEdition,
+        /// The learner already studies the current edition.
+        AlreadyOnCurrentEdition,
+        /// The learner has not started the course yet.
+        CourseNotStarted,
+        /// Learners and course owners cannot refer their own enrollment.
+        InvalidReferrer,
+        /// Handles arithmetic overflow when incrementing the id Nonce.
+        NonceOverflow,
+        /// A Course with the same id already exists.
+        CourseAlreadyExists,
+        /// A Lecture with the same id already exists in the course.
+        LectureAlreadyExists,
+        /// The lecture contents are not encrypted.
+        LectureNotEncrypted,
+        /// The learner has not registered an encryption key.
+        EncryptionKeyNotRegistered,
+        /// The wrapped key is longer than `MaxWrappedKeyLen`.
+        WrappedKeyTooLong,
+        /// The content key of the lecture was not granted to the learner yet.
+        LectureKeyNotGranted,
+        /// Only verified instructors can publish courses.
+        InstructorNotVerified,
+        /// Handles arithmetic overflow when incrementing the Section counter.
+        SectionCntOverflow,
+        /// The language tag is longer than `MaxLanguageTagLen`.
+        LanguageTagTooLong,
+        /// Encrypted lectures cannot be translated, the translation would be stored in clear.
+        LectureEncrypted,
+        /// Only the course owner and its translators can manage translations.
+        NotTranslator,
+        /// The account is already a translator of the course.
+        AlreadyTranslator,
+        /// Handles checking whether the Translation exists.
+        TranslationNotExist,
+        /// A course or lecture cannot be translated into more than `MaxTranslations` languages.
+        TooManyTranslations,
+        /// The license of the course does not allow derivatives.
+        LicenseForbidsForking,
+        /// Derivatives of share-alike courses must keep the share-alike license.
+        ShareAlikeRequired,
+        /// Only the class owner can manage the class.
+        NotClassOwner,
+        /// The royalty exceeds `MaxClassRoyalty`.
+        RoyaltyTooHigh,
+        /// Token not found
+        TokenNotFound,
+        /// Only the token owner can sell or transfer the token.
+        NotTokenOwner,
+        /// Tokens of `BoundToAddress` classes cannot change hands or be burned.
+        TokenNotTransferable,
+        /// The token is not listed for sale.
+        TokenNotForSale,
+        /// Buyer cannot be the token owner.
+        BuyerIsTokenOwner,
+        /// The bid price is lower than the listing price.
+        TokenBidPriceTooLow,
+        /// A class with the same ID already exists.
+        ClassAlreadyExists,
+        /// Classes are administered by their owner.
+        ClassAdminNotOwner,
+        /// A token with the same ID already exists in the class.
+        TokenAlreadyExists,
+        /// Handles checking whether the BadgeRule exists.
+        BadgeRuleNotExist,
+        /// A course, or governance, cannot define more active badge rules than `MaxBadgeRules`.
+        TooManyBadgeRules,
+        /// Handles arithmetic overflow when incrementing the BadgeRule counter.
+        BadgeRuleCntOverflow,
     }
 
     #[pallet::event]
@@ -185,6 +904,139 @@ pub mod pallet {
         Transferred(T::AccountId, T::AccountId, T::Hash),
         /// A Course was successfully bought. \[buyer, seller, course_id, bid_price\]
         Bought(T::AccountId, T::AccountId, T::Hash, BalanceOf<T>),
+        /// A Lecture was completed. \[learner, course_id, lecture_id\]
+        LectureCompleted(T::AccountId, T::Hash, T::Hash),
+        /// Every lecture of a Course was completed. \[learner, course_id\]
+        CourseCompleted(T::AccountId, T::Hash),
+        /// A reward plan was attached to a Course. \[course_id, per_lecture, completion_bonus, learner_cap\]
+        RewardPlanSet(T::Hash, BalanceOf<T>, BalanceOf<T>, BalanceOf<T>),
+        /// A reward plan was removed from a Course. \[course_id\]
+        RewardPlanRemoved(T::Hash),
+        /// The reward pot of a Course was funded. \[funder, course_id, amount\]
+        RewardsFunded(T::AccountId, T::Hash, BalanceOf<T>),
+        /// A learner was rewarded from the Course reward pot. \[learner, course_id, amount\]
+        RewardPaid(T::AccountId, T::Hash, BalanceOf<T>),
+        /// The reward pot of a Course was swept back to the Treasury. \[course_id, amount\]
+        RewardsSwept(T::Hash, BalanceOf<T>),
+        /// A Course was unpublished and its bond released. \[sender, course_id\]
+        Unpublished(T::AccountId, T::Hash),
+        /// A complaint was filed against a Course. \[reporter, course_id, complaint_id, evidence\]
+        ComplaintFiled(T::AccountId, T::Hash, u32, T::Hash),
+        /// A complaint was dismissed and its deposit slashed. \[course_id, complaint_id, slashed\]
+        ComplaintDismissed(T::Hash, u32, BalanceOf<T>),
+        /// A complaint expired and its deposit was refunded. \[course_id, complaint_id\]
+        ComplaintExpired(T::Hash, u32),
+        /// A Course was taken down and its bond slashed. \[course_id, slashed\]
+        TakenDown(T::Hash, BalanceOf<T>),
+        /// A new Cohort was created. \[course_id, cohort_id\]
+        CohortCreated(T::Hash, CohortId),
+        /// A Lecture due date was set for a Cohort. \[course_id, cohort_id, lecture_id, due\]
+        LectureDueDateSet(T::Hash, CohortId, T::Hash, MomentOf<T>),
+        /// An account joined a Cohort. \[learner, course_id, cohort_id\]
+        CohortJoined(T::AccountId, T::Hash, CohortId),
+        /// An account left, or was removed from, a Cohort. \[learner, course_id, cohort_id\]
+        CohortLeft(T::AccountId, T::Hash, CohortId),
+        /// A Lecture was completed within a Cohort. \[learner, course_id, cohort_id, lecture_id, late\]
+        CohortLectureCompleted(T::AccountId, T::Hash, CohortId, T::Hash, bool),
+        /// A Cohort was closed. \[course_id, cohort_id\]
+        CohortClosed(T::Hash, CohortId),
+        /// An account joined the reviewer pool of a Course. \[reviewer, course_id\]
+        ReviewerJoined(T::AccountId, T::Hash),
+        /// An account left the reviewer pool of a Course. \[reviewer, course_id\]
+        ReviewerLeft(T::AccountId, T::Hash),
+        /// The owner of a Course approved an account as reviewer. \[reviewer, course_id\]
+        ReviewerApproved(T::AccountId, T::Hash),
+        /// The reviewers who did not score a submission in time were replaced. \[learner, course_id, lecture_id, reviewers\]
+        ReviewersReassigned(T::AccountId, T::Hash, T::Hash, Vec<T::AccountId>),
+        /// A submission was withdrawn after its review period. \[learner, course_id, lecture_id\]
+        SubmissionWithdrawn(T::AccountId, T::Hash, T::Hash),
+        /// An assignment was submitted for review. \[learner, course_id, lecture_id, reviewers\]
+        AssignmentSubmitted(T::AccountId, T::Hash, T::Hash, Vec<T::AccountId>),
+        /// A submission was scored. \[reviewer, learner, course_id, lecture_id, score\]
+        AssignmentReviewed(T::AccountId, T::AccountId, T::Hash, T::Hash, u8),
+        /// Every reviewer scored a submission. \[learner, course_id, lecture_id, median, passed\]
+        AssignmentGraded(T::AccountId, T::Hash, T::Hash, u8, bool),
+        /// A passed assignment could not be completed yet, the learner can complete it later
+        /// with `complete_lecture`. \[learner, course_id, lecture_id\]
+        CompletionDeferred(T::AccountId, T::Hash, T::Hash),
+        /// Course price was successfully set. \[sender, course_id, new_price, currency\]
+        PriceSet(T::AccountId, T::Hash, Option<BalanceOf<T>>, PaymentCurrency<T::AssetId>),
+        /// An account enrolled in a Course. \[learner, course_id, paid\]
+        Enrolled(T::AccountId, T::Hash, BalanceOf<T>),
+        /// A new LearningPath was created. \[owner, path_id, certificate_class\]
+        LearningPathCreated(T::AccountId, PathId, ClassId),
+        /// An account enrolled in every Course of a LearningPath. \[learner, path_id, currency, paid\]
+        PathEnrolled(T::AccountId, PathId, PaymentCurrency<T::AssetId>, BalanceOf<T>),
+        /// A LearningPath was completed and its certificate issued. \[learner, path_id, class_id, token_id\]
+        PathCompleted(T::AccountId, PathId, ClassId, TokenId),
+        /// A new Coupon was created. \[owner, code\]
+        CouponCreated(T::AccountId, T::Hash),
+        /// A Coupon was revoked. \[owner, code\]
+        CouponRevoked(T::AccountId, T::Hash),
+        /// A Coupon was redeemed on enrollment. \[learner, code, course_id, discounted_price\]
+        CouponRedeemed(T::AccountId, T::Hash, T::Hash, BalanceOf<T>),
+        /// The enrollment rules of a Course were set. \[course_id\]
+        EnrollmentRulesSet(T::Hash),
+        /// An enrollment was gifted. \[payer, recipient, course_id, paid, accepted\]
+        EnrollmentGifted(T::AccountId, T::AccountId, T::Hash, BalanceOf<T>, bool),
+        /// A pending gift was accepted by its recipient. \[payer, recipient, course_id\]
+        GiftAccepted(T::AccountId, T::AccountId, T::Hash),
+        /// A pending gift was cancelled and refunded. \[payer, recipient, course_id\]
+        GiftCancelled(T::AccountId, T::AccountId, T::Hash),
+        /// The release schedule of a Lecture was set. \[course_id, lecture_id, release\]
+        LectureReleaseSet(T::Hash, T::Hash, Option<ReleaseSchedule<T::BlockNumber>>),
+        /// A Lecture was successfully updated. \[sender, course_id, lecture_id\]
+        LectureUpdated(T::AccountId, T::Hash, T::Hash),
+        /// A new Edition of a Course was published. \[course_id, edition\]
+        EditionPublished(T::Hash, EditionId),
+        /// A learner moved their progress to a newer Edition. \[learner, course_id, from, to, kept\]
+        ProgressMigrated(T::AccountId, T::Hash, EditionId, EditionId, u32),
+        /// The referral commission of a Course was set. \[sender, course_id, commission\]
+        ReferralCommissionSet(T::AccountId, T::Hash, Perbill),
+        /// A referrer was paid a commission on a sale. \[referrer, learner, course_id, commission\]
+        ReferralPaid(T::AccountId, T::AccountId, T::Hash, BalanceOf<T>),
+        /// An account registered its encryption key. \[account, public_key\]
+        EncryptionKeyRegistered(T::AccountId, [u8; 32]),
+        /// An encrypted Lecture was created. \[sender, course_id, lecture_id, key_commitment\]
+        EncryptedLectureCreated(T::AccountId, T::Hash, T::Hash, T::Hash),
+        /// The content key of a Lecture was granted to a learner. \[course_id, lecture_id, learner\]
+        LectureKeyGranted(T::Hash, T::Hash, T::AccountId),
+        /// A Section of lectures was added to a Course. \[course_id, section_id, lectures\]
+        SectionCreated(T::Hash, SectionId, u32),
+        /// A translator was assigned to a Course. \[course_id, translator\]
+        TranslatorAdded(T::Hash, T::AccountId),
+        /// A translator was removed from a Course. \[course_id, translator\]
+        TranslatorRemoved(T::Hash, T::AccountId),
+        /// A translation of a Course was set. \[sender, course_id, language\]
+        CourseTranslationSet(T::AccountId, T::Hash, Vec<u8>),
+        /// A translation of a Lecture was set. \[sender, course_id, lecture_id, language\]
+        LectureTranslationSet(T::AccountId, T::Hash, T::Hash, Vec<u8>),
+        /// A translation was removed. \[sender, course_id, lecture_id, language\]
+        TranslationRemoved(T::AccountId, T::Hash, Option<T::Hash>, Vec<u8>),
+        /// The license of a Course was set. \[sender, course_id, license\]
+        LicenseSet(T::AccountId, T::Hash, License),
+        /// A Course was forked into a derivative. \[sender, parent_id, course_id\]
+        CourseForked(T::AccountId, T::Hash, T::Hash),
+        /// A royalty was paid to the owner of the parent of a Course. \[beneficiary, course_id, amount\]
+        DerivativeRoyaltyPaid(T::AccountId, T::Hash, BalanceOf<T>),
+        /// The royalty of an NFT class was set. \[class_id, royalty\]
+        ClassRoyaltySet(ClassId, Option<ClassRoyalty<T::AccountId>>),
+        /// A royalty was paid on the sale of a token. \[beneficiary, class_id, token_id, amount\]
+        TokenRoyaltyPaid(T::AccountId, ClassId, TokenId, BalanceOf<T>),
+        /// A token was listed for sale, or its price updated. \[seller, class_id, token_id, price\]
+        TokenListed(T::AccountId, ClassId, TokenId, BalanceOf<T>),
+        /// A token was removed from sale. \[seller, class_id, token_id\]
+        TokenDelisted(T::AccountId, ClassId, TokenId),
+        /// A token was sold. \[buyer, seller, class_id, token_id, price\]
+        TokenBought(T::AccountId, T::AccountId, ClassId, TokenId, BalanceOf<T>),
+        /// A token was transferred. \[from, to, class_id, token_id\]
+        TokenTransferred(T::AccountId, T::AccountId, ClassId, TokenId),
+        /// A badge rule was created. \[rule_id, course_id, class_id\]
+        BadgeRuleCreated(BadgeRuleId, Option<T::Hash>, ClassId),
+        /// A badge rule was removed. \[rule_id\]
+        BadgeRuleRemoved(BadgeRuleId),
+        /// A learner earned a badge. \[learner, rule_id, class_id, token_id\]
+        BadgeAwarded(T::AccountId, BadgeRuleId, ClassId, TokenId),
     }
 
     // Storage items.
@@ -206,6 +1058,7 @@ pub mod pallet {
 
     #[pallet::storage]
     #[pallet::getter(fn get_nonce)]
+    /// Monotonic counter Course and Lecture ids are derived from.
     pub(super) type Nonce<T: Config> = StorageValue<_, u64, ValueQuery>;
 
     #[pallet::storage]
@@ -233,201 +1086,2184 @@ pub mod pallet {
     >;
 
     #[pallet::storage]
-    #[pallet::getter(fn courses_owned)]
-    /// Keeps track of what accounts own what Course.
-    pub(super) type CoursesOwned<T: Config> =
-    StorageMap<_, Twox64Concat, T::AccountId, BoundedVec<T::Hash, T::MaxCourseOwned>, ValueQuery>;
-
-
-    /// Next available class ID.
-    #[pallet::storage]
-    #[pallet::getter(fn next_class_id)]
-    pub type NextClassId<T: Config> = StorageValue<_, ClassId, ValueQuery>;
-
-    /// Next available token ID.
-    #[pallet::storage]
-    #[pallet::getter(fn next_token_id)]
-    pub type NextTokenId<T: Config> = StorageMap<_, Twox64Concat, ClassId, TokenId, ValueQuery>;
+    #[pallet::getter(fn courses_completed)]
+    /// Stores the Courses every account has completed.
+    pub(super) type CoursesCompleted<T: Config> = StorageDoubleMap<
+        _,
+        Twox64Concat, T::AccountId,
+        Twox64Concat, T::Hash,
+        CourseCompletion<T>,
+    >;
 
-    /// Store class info.
-    ///
-    /// Returns `None` if class info not set or removed.
     #[pallet::storage]
-    #[pallet::getter(fn classes)]
-    pub type Classes<T: Config> = StorageMap<_, Twox64Concat, ClassId, ClassInfoOf<T>>;
+    #[pallet::getter(fn reward_plans)]
+    /// Stores the learn-to-earn reward plan of a Course.
+    pub(super) type RewardPlans<T: Config> = StorageMap<_, Twox64Concat, T::Hash, RewardPlan<T>>;
 
-    /// Store token info.
-    ///
-    /// Returns `None` if token info not set or removed.
     #[pallet::storage]
-    #[pallet::getter(fn tokens)]
-    pub type Tokens<T: Config> =
-    StorageDoubleMap<_, Twox64Concat, ClassId, Twox64Concat, TokenId, TokenInfoOf<T>>;
+    #[pallet::getter(fn rewards_claimed)]
+    /// Keeps track of the rewards every account has earned from a Course.
+    pub(super) type RewardsClaimed<T: Config> =
+    StorageDoubleMap<_, Twox64Concat, T::AccountId, Twox64Concat, T::Hash, BalanceOf<T>, ValueQuery>;
 
-    /// Token existence check by owner and class ID.
     #[pallet::storage]
-    #[pallet::getter(fn tokens_by_owner)]
-    pub type TokensByOwner<T: Config> = StorageNMap<
+    #[pallet::getter(fn rewarded_lectures)]
+    /// Keeps track of the lectures every account has been rewarded for, across editions.
+    pub(super) type RewardedLectures<T: Config> = StorageNMap<
         _,
         (
-            NMapKey<Blake2_128Concat, T::AccountId>, // owner
-            NMapKey<Blake2_128Concat, ClassId>,
-            NMapKey<Blake2_128Concat, TokenId>,
+            NMapKey<Twox64Concat, T::AccountId>, // Account
+            NMapKey<Twox64Concat, T::Hash>, // Course
+            NMapKey<Twox64Concat, T::Hash>, // Lecture
         ),
         (),
-        ValueQuery,
+        OptionQuery,
     >;
 
+    #[pallet::storage]
+    #[pallet::getter(fn course_bonds)]
+    /// Stores the bond reserved by the instructor of a published Course.
+    pub(super) type CourseBonds<T: Config> =
+    StorageMap<_, Twox64Concat, T::Hash, (T::AccountId, BalanceOf<T>)>;
 
-    // ACTION #11: Our pallet's genesis configuration.
-    // Our pallet's genesis configuration.
-    #[pallet::genesis_config]
-    pub struct GenesisConfig<T: Config> {
-        pub courses: Vec<(T::AccountId, Vec<u8>, Vec<u8>, Vec<u8>, Vec<u8>)>,
-    }
+    #[pallet::storage]
+    #[pallet::getter(fn complaints)]
+    /// Stores the open complaints filed against a Course.
+    pub(super) type Complaints<T: Config> =
+    StorageDoubleMap<_, Twox64Concat, T::Hash, Twox64Concat, u32, Complaint<T>>;
 
-    // Required to implement default for GenesisConfig.
-    #[cfg(feature = "std")]
-    impl<T: Config> Default for GenesisConfig<T> {
-        fn default() -> GenesisConfig<T> {
-            GenesisConfig { courses: vec![] }
-        }
-    }
+    #[pallet::storage]
+    #[pallet::getter(fn next_complaint_id)]
+    /// Next available complaint ID of a Course.
+    pub(super) type NextComplaintId<T: Config> = StorageMap<_, Twox64Concat, T::Hash, u32, ValueQuery>;
 
-    #[pallet::genesis_build]
-    impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
-        fn build(&self) {
-            // When building a course from genesis config, we require the dna and gender to be supplied.
-            for (acct, name, category, image_url, description) in &self.courses {
-                let _ = <Pallet<T>>::mint(acct, name.clone(), category.clone(), image_url.clone(), description.clone());
-            }
-        }
-    }
+    #[pallet::storage]
+    #[pallet::getter(fn open_complaints)]
+    /// Keeps track of the number of open complaints against a Course.
+    pub(super) type OpenComplaints<T: Config> = StorageMap<_, Twox64Concat, T::Hash, u32, ValueQuery>;
 
+    #[pallet::storage]
+    #[pallet::getter(fn taken_down)]
+    /// Stores the block at which a Course was taken down.
+    pub(super) type TakenDown<T: Config> = StorageMap<_, Twox64Concat, T::Hash, T::BlockNumber>;
 
-    #[pallet::call]
-    impl<T: Config> Pallet<T> {
-        /// Create a new unique course.
-        ///
-        /// The actual course creation is done in the `mint()` function.
+    #[pallet::storage]
+    #[pallet::getter(fn cohorts)]
+    /// Stores the Cohorts of a Course.
+    pub(super) type Cohorts<T: Config> =
+    StorageDoubleMap<_, Twox64Concat, T::Hash, Twox64Concat, CohortId, Cohort<T>>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn next_cohort_id)]
+    /// Next available cohort ID of a Course.
+    pub(super) type NextCohortId<T: Config> = StorageMap<_, Twox64Concat, T::Hash, CohortId, ValueQuery>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn lecture_due_dates)]
+    /// Stores the due date of a Lecture within a Cohort.
+    pub(super) type LectureDueDates<T: Config> = StorageNMap<
+        _,
+        (
+            NMapKey<Twox64Concat, T::Hash>, // Course
+            NMapKey<Twox64Concat, CohortId>, // Cohort
+            NMapKey<Twox64Concat, T::Hash>, // Lecture
+        ),
+        MomentOf<T>,
+        OptionQuery,
+    >;
+
+    #[pallet::storage]
+    #[pallet::getter(fn cohort_members)]
+    /// Keeps track of the Cohort every account joined for a Course.
+    pub(super) type CohortMembers<T: Config> =
+    StorageDoubleMap<_, Twox64Concat, T::Hash, Twox64Concat, T::AccountId, CohortId>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn cohort_completions)]
+    /// Stores the Lectures completed by the members of a Cohort.
+    pub(super) type CohortCompletions<T: Config> = StorageNMap<
+        _,
+        (
+            NMapKey<Twox64Concat, T::Hash>, // Course
+            NMapKey<Twox64Concat, CohortId>, // Cohort
+            NMapKey<Twox64Concat, T::AccountId>, // Account
+            NMapKey<Twox64Concat, T::Hash>, // Lecture
+        ),
+        CohortCompletion<T>,
+        OptionQuery,
+    >;
+
+    #[pallet::storage]
+    #[pallet::getter(fn reviewer_pool)]
+    /// Keeps track of the accounts volunteering to review the assignments of a Course.
+    pub(super) type ReviewerPool<T: Config> =
+    StorageMap<_, Twox64Concat, T::Hash, BoundedVec<T::AccountId, T::MaxReviewerPool>, ValueQuery>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn approved_reviewers)]
+    /// Keeps track of the accounts the owner of a Course approved as reviewers.
+    pub(super) type ApprovedReviewers<T: Config> =
+    StorageDoubleMap<_, Twox64Concat, T::Hash, Twox64Concat, T::AccountId, ()>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn lecture_key_commitments)]
+    /// Commitment to the content key of encrypted Lectures, whose contents hold the ciphertext.
+    pub(super) type LectureKeyCommitments<T: Config> =
+    StorageDoubleMap<_, Twox64Concat, T::Hash, Twox64Concat, T::Hash, T::Hash>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn encryption_keys)]
+    /// Stores the public key content keys are wrapped to, per account.
+    pub(super) type EncryptionKeys<T: Config> = StorageMap<_, Twox64Concat, T::AccountId, [u8; 32]>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn wrapped_keys)]
+    /// Stores the content keys of encrypted Lectures wrapped for each learner granted access.
+    pub(super) type WrappedKeys<T: Config> = StorageNMap<
+        _,
+        (
+            NMapKey<Twox64Concat, T::Hash>, // Course
+            NMapKey<Twox64Concat, T::Hash>, // Lecture
+            NMapKey<Twox64Concat, T::AccountId>, // Learner
+        ),
+        WrappedKey<T>,
+        OptionQuery,
+    >;
+
+    #[pallet::storage]
+    #[pallet::getter(fn submissions)]
+    /// Stores the assignment submissions awaiting review.
+    pub(super) type Submissions<T: Config> = StorageNMap<
+        _,
+        (
+            NMapKey<Twox64Concat, T::Hash>, // Course
+            NMapKey<Twox64Concat, T::Hash>, // Lecture
+            NMapKey<Twox64Concat, T::AccountId>, // Learner
+        ),
+        Submission<T>,
+        OptionQuery,
+    >;
+
+    #[pallet::storage]
+    #[pallet::getter(fn passed_assignments)]
+    /// Stores the passed assignments whose completion could not be recorded when graded.
+    pub(super) type PassedAssignments<T: Config> = StorageNMap<
+        _,
+        (
+            NMapKey<Twox64Concat, T::Hash>, // Course
+            NMapKey<Twox64Concat, T::Hash>, // Lecture
+            NMapKey<Twox64Concat, T::AccountId>, // Learner
+        ),
+        (),
+        OptionQuery,
+    >;
+
+    #[pallet::storage]
+    #[pallet::getter(fn reviewer_reputation)]
+    /// Stores the reputation of every reviewer.
+    pub(super) type ReviewerReputation<T: Config> =
+    StorageMap<_, Twox64Concat, T::AccountId, Reputation, ValueQuery>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn enrollments)]
+    /// Stores the Courses every account is enrolled in.
+    pub(super) type Enrollments<T: Config> =
+    StorageDoubleMap<_, Twox64Concat, T::AccountId, Twox64Concat, T::Hash, Enrollment<T>>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn learning_paths)]
+    /// Stores the LearningPaths.
+    pub(super) type LearningPaths<T: Config> = StorageMap<_, Twox64Concat, PathId, LearningPath<T>>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn next_path_id)]
+    /// Next available learning path ID.
+    pub(super) type NextPathId<T: Config> = StorageValue<_, PathId, ValueQuery>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn path_enrollments)]
+    /// Keeps track of the LearningPaths every account is enrolled in.
+    pub(super) type PathEnrollments<T: Config> =
+    StorageDoubleMap<_, Twox64Concat, T::AccountId, Twox64Concat, PathId, T::BlockNumber>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn learner_path_count)]
+    /// Keeps track of the number of LearningPaths every account is enrolled in.
+    pub(super) type LearnerPathCount<T: Config> = StorageMap<_, Twox64Concat, T::AccountId, u32, ValueQuery>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn path_certificates)]
+    /// Stores the certificate token issued to every account completing a LearningPath.
+    pub(super) type PathCertificates<T: Config> =
+    StorageDoubleMap<_, Twox64Concat, T::AccountId, Twox64Concat, PathId, TokenId>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn coupons)]
+    /// Stores the Coupons by the hash of their code.
+    pub(super) type Coupons<T: Config> = StorageMap<_, Twox64Concat, T::Hash, Coupon<T>>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn coupon_redemptions)]
+    /// Keeps track of the accounts that redeemed a Coupon.
+    pub(super) type CouponRedemptions<T: Config> =
+    StorageDoubleMap<_, Twox64Concat, T::Hash, Twox64Concat, T::AccountId, T::BlockNumber>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn enrollment_rules)]
+    /// Stores the prerequisites and capacity of a Course.
+    pub(super) type CourseEnrollmentRules<T: Config> = StorageMap<_, Twox64Concat, T::Hash, EnrollmentRules<T>>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn enrollment_count)]
+    /// Keeps track of the number of enrollments of a Course.
+    pub(super) type EnrollmentCount<T: Config> = StorageMap<_, Twox64Concat, T::Hash, u32, ValueQuery>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn completion_count)]
+    /// Keeps track of the number of learners who completed a Course.
+    pub(super) type CompletionCount<T: Config> = StorageMap<_, Twox64Concat, T::Hash, u32, ValueQuery>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn lecture_completion_count)]
+    /// Keeps track of the number of learners who completed a Lecture.
+    pub(super) type LectureCompletionCount<T: Config> =
+    StorageDoubleMap<_, Twox64Concat, T::Hash, Twox64Concat, T::Hash, u32, ValueQuery>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn course_revenue)]
+    /// Keeps track of the gross revenue of a Course, per payment currency.
+    pub(super) type CourseRevenue<T: Config> = StorageDoubleMap<
+        _,
+        Twox64Concat,
+        T::Hash,
+        Twox64Concat,
+        PaymentCurrency<T::AssetId>,
+        BalanceOf<T>,
+        ValueQuery,
+    >;
+
+    #[pallet::storage]
+    #[pallet::getter(fn referral_commissions)]
+    /// Share of the sale paid to the referrer of an enrollment, per course.
+    pub(super) type ReferralCommissions<T: Config> = StorageMap<_, Twox64Concat, T::Hash, Perbill, ValueQuery>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn referral_stats)]
+    /// Keeps track of the referrals of every affiliate, per payment currency.
+    pub(super) type ReferralStatistics<T: Config> = StorageDoubleMap<
+        _,
+        Twox64Concat,
+        T::AccountId,
+        Twox64Concat,
+        PaymentCurrency<T::AssetId>,
+        ReferralStats<BalanceOf<T>>,
+        ValueQuery,
+    >;
+
+    #[pallet::storage]
+    #[pallet::getter(fn pending_gifts)]
+    /// Stores the gifted enrollments awaiting their recipient.
+    pub(super) type PendingGifts<T: Config> =
+    StorageDoubleMap<_, Twox64Concat, T::AccountId, Twox64Concat, T::Hash, Gift<T>>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn editions)]
+    /// Stores the lecture snapshot of every published Edition of a Course.
+    pub(super) type Editions<T: Config> =
+    StorageDoubleMap<_, Twox64Concat, T::Hash, Twox64Concat, EditionId, Edition<T>>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn badge_rules)]
+    /// Stores the badge rules.
+    pub(super) type BadgeRules<T: Config> = StorageMap<_, Twox64Concat, BadgeRuleId, BadgeRule<T>>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn active_badge_rules)]
+    /// Keeps track of the badge rules of a Course, `None` for the global rules, evaluated on
+    /// each completion.
+    pub(super) type ActiveBadgeRules<T: Config> =
+    StorageMap<_, Twox64Concat, Option<T::Hash>, BoundedVec<BadgeRuleId, T::MaxBadgeRules>, ValueQuery>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn next_badge_rule_id)]
+    pub(super) type NextBadgeRuleId<T: Config> = StorageValue<_, BadgeRuleId, ValueQuery>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn badges_awarded)]
+    /// Keeps track of the badge token each learner earned, per rule.
+    pub(super) type BadgesAwarded<T: Config> =
+    StorageDoubleMap<_, Twox64Concat, T::AccountId, Twox64Concat, BadgeRuleId, TokenId>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn learner_lecture_count)]
+    /// Keeps track of the number of lectures an account completed, over all courses.
+    pub(super) type LearnerLectureCount<T: Config> = StorageMap<_, Twox64Concat, T::AccountId, u32, ValueQuery>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn course_lecture_count)]
+    /// Keeps track of the number of lectures of a Course an account currently has completed.
+    pub(super) type CourseLectureCount<T: Config> =
+    StorageDoubleMap<_, Twox64Concat, T::AccountId, Twox64Concat, T::Hash, u32, ValueQuery>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn course_starts)]
+    /// Keeps track of when an account completed its first lecture of a Course.
+    pub(super) type CourseStarts<T: Config> =
+    StorageDoubleMap<_, Twox64Concat, T::AccountId, Twox64Concat, T::Hash, MomentOf<T>>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn category_completions)]
+    /// Keeps track of the number of courses an account completed, per hash of the category.
+    pub(super) type CategoryCompletions<T: Config> =
+    StorageDoubleMap<_, Twox64Concat, T::AccountId, Twox64Concat, T::Hash, u32, ValueQuery>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn streaks)]
+    /// Keeps track of the daily activity streak of every account.
+    pub(super) type Streaks<T: Config> = StorageMap<_, Twox64Concat, T::AccountId, Streak, ValueQuery>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn longest_streaks)]
+    /// Keeps track of the longest daily activity streak of every account.
+    pub(super) type LongestStreaks<T: Config> = StorageMap<_, Twox64Concat, T::AccountId, u32, ValueQuery>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn course_scores)]
+    /// Keeps track of the sum of the median scores of the passed assignments of a learner.
+    pub(super) type CourseScores<T: Config> =
+    StorageDoubleMap<_, Twox64Concat, T::Hash, Twox64Concat, T::AccountId, u32, ValueQuery>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn leaderboards)]
+    /// Stores the top learners of a Course, best first, per ranking criteria.
+    pub(super) type Leaderboards<T: Config> = StorageDoubleMap<
+        _,
+        Twox64Concat,
+        T::Hash,
+        Twox64Concat,
+        LeaderboardKind,
+        BoundedVec<(T::AccountId, u64), T::MaxLeaderboardSize>,
+        ValueQuery,
+    >;
+
+    #[pallet::storage]
+    #[pallet::getter(fn translators)]
+    /// Keeps track of the accounts allowed to translate a Course.
+    pub(super) type Translators<T: Config> =
+    StorageDoubleMap<_, Twox64Concat, T::Hash, Twox64Concat, T::AccountId, (), OptionQuery>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn course_translations)]
+    /// Stores the translations of the metadata of a Course, per language.
+    pub(super) type CourseTranslations<T: Config> =
+    StorageDoubleMap<_, Twox64Concat, T::Hash, Blake2_128Concat, LanguageTag<T>, Translation>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn translation_count)]
+    /// Keeps track of the number of languages a Course, or one of its Lectures, is translated into.
+    pub(super) type TranslationCount<T: Config> =
+    StorageDoubleMap<_, Twox64Concat, T::Hash, Twox64Concat, Option<T::Hash>, u32, ValueQuery>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn lecture_translations)]
+    /// Stores the translations of a Lecture, per language.
+    pub(super) type LectureTranslations<T: Config> = StorageNMap<
+        _,
+        (
+            NMapKey<Twox64Concat, T::Hash>, // Course
+            NMapKey<Twox64Concat, T::Hash>, // Lecture
+            NMapKey<Blake2_128Concat, LanguageTag<T>>, // Language
+        ),
+        Translation,
+        OptionQuery,
+    >;
+
+    #[pallet::storage]
+    #[pallet::getter(fn sections)]
+    /// Stores the Sections grouping the lectures of a Course.
+    pub(super) type Sections<T: Config> =
+    StorageDoubleMap<_, Twox64Concat, T::Hash, Twox64Concat, SectionId, Section<T>>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn next_section_id)]
+    /// Keeps track of the next Section id of a Course.
+    pub(super) type NextSectionId<T: Config> = StorageMap<_, Twox64Concat, T::Hash, SectionId, ValueQuery>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn lecture_sections)]
+    /// Keeps track of the Section a Lecture belongs to.
+    pub(super) type LectureSections<T: Config> =
+    StorageDoubleMap<_, Twox64Concat, T::Hash, Twox64Concat, T::Hash, SectionId>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn current_edition)]
+    /// Keeps track of the latest Edition of a Course, `0` until the course is first published.
+    pub(super) type CurrentEdition<T: Config> = StorageMap<_, Twox64Concat, T::Hash, EditionId, ValueQuery>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn learner_editions)]
+    /// Keeps track of the Edition every account studies.
+    pub(super) type LearnerEditions<T: Config> =
+    StorageDoubleMap<_, Twox64Concat, T::AccountId, Twox64Concat, T::Hash, EditionId>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn courses_owned)]
+    /// Keeps track of what accounts own what Course.
+    pub(super) type CoursesOwned<T: Config> =
+    StorageMap<_, Twox64Concat, T::AccountId, BoundedVec<T::Hash, T::MaxCourseOwned>, ValueQuery>;
+
+
+    /// Next available class ID.
+    #[pallet::storage]
+    #[pallet::getter(fn next_class_id)]
+    pub type NextClassId<T: Config> = StorageValue<_, ClassId, ValueQuery>;
+
+    /// Next available token ID.
+    #[pallet::storage]
+    #[pallet::getter(fn next_token_id)]
+    pub type NextTokenId<T: Config> = StorageMap<_, Twox64Concat, ClassId, TokenId, ValueQuery>;
+
+    /// Store class info.
+    ///
+    /// Returns `None` if class info not set or removed.
+    #[pallet::storage]
+    #[pallet::getter(fn classes)]
+    pub type Classes<T: Config> = StorageMap<_, Twox64Concat, ClassId, ClassInfoOf<T>>;
+
+    /// Store token info.
+    ///
+    /// Returns `None` if token info not set or removed.
+    #[pallet::storage]
+    #[pallet::getter(fn tokens)]
+    pub type Tokens<T: Config> =
+    StorageDoubleMap<_, Twox64Concat, ClassId, Twox64Concat, TokenId, TokenInfoOf<T>>;
+
+    /// Store the royalty paid on secondary sales of the tokens of a class.
+    #[pallet::storage]
+    #[pallet::getter(fn class_royalties)]
+    pub type ClassRoyalties<T: Config> = StorageMap<_, Twox64Concat, ClassId, ClassRoyalty<T::AccountId>>;
+
+    /// Store the price and royalty terms of the tokens listed for sale.
+    #[pallet::storage]
+    #[pallet::getter(fn listings)]
+    pub type Listings<T: Config> =
+    StorageDoubleMap<_, Twox64Concat, ClassId, Twox64Concat, TokenId, Listing<T>>;
+
+    /// Token existence check by owner and class ID.
+    #[pallet::storage]
+    #[pallet::getter(fn tokens_by_owner)]
+    pub type TokensByOwner<T: Config> = StorageNMap<
+        _,
+        (
+            NMapKey<Blake2_128Concat, T::AccountId>, // owner
+            NMapKey<Blake2_128Concat, ClassId>,
+            NMapKey<Blake2_128Concat, TokenId>,
+        ),
+        (),
+        ValueQuery,
+    >;
+
+
+    /// A Lecture seeded at genesis.
+    #[derive(Clone, PartialEq, Eq, RuntimeDebug)]
+    #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+    pub struct GenesisLecture {
+        pub name: Vec<u8>,
+        pub contents: Vec<u8>,
+        pub kind: LectureKind,
+    }
+
+    /// A Course seeded at genesis, with its lectures and enrolled learners.
+    #[derive(Clone, PartialEq, Eq, RuntimeDebug)]
+    #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+    pub struct GenesisCourse<AccountId, Balance> {
+        pub owner: AccountId,
+        pub name: Vec<u8>,
+        pub category: Vec<u8>,
+        pub image_url: Vec<u8>,
+        pub description: Vec<u8>,
+        pub price: Option<Balance>,
+        pub lectures: Vec<GenesisLecture>,
+        /// Publish the course, reserving the course bond from the owner.
+        pub live: bool,
+        /// Learners enrolled without paying.
+        pub enrollments: Vec<AccountId>,
+    }
+
+    /// An NFT class seeded at genesis, with the owners of its tokens.
+    #[derive(Clone, PartialEq, Eq, RuntimeDebug)]
+    #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+    pub struct GenesisClass<AccountId> {
+        pub owner: AccountId,
+        pub token_type: TokenType,
+        pub tokens: Vec<AccountId>,
+    }
+
+    // ACTION #11: Our pallet's genesis configuration.
+    // Our pallet's genesis configuration.
+    #[pallet::genesis_config]
+    pub struct GenesisConfig<T: Config> {
+        pub courses: Vec<GenesisCourse<T::AccountId, BalanceOf<T>>>,
+        pub classes: Vec<GenesisClass<T::AccountId>>,
+    }
+
+    // Required to implement default for GenesisConfig.
+    #[cfg(feature = "std")]
+    impl<T: Config> Default for GenesisConfig<T> {
+        fn default() -> GenesisConfig<T> {
+            GenesisConfig { courses: vec![], classes: vec![] }
+        }
+    }
+
+    #[pallet::genesis_build]
+    impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
+        fn build(&self) {
+            // Invalid genesis data is a misconfigured chain spec, so every failure panics.
+            for course in &self.courses {
+                let course_id = <Pallet<T>>::mint(
+                    &course.owner,
+                    course.name.clone(),
+                    course.category.clone(),
+                    course.image_url.clone(),
+                    course.description.clone(),
+                ).expect("genesis course can be minted");
+                <Courses<T>>::mutate(&course_id, |c| {
+                    if let Some(info) = c {
+                        info.price = course.price;
+                    }
+                });
+
+                for lecture in &course.lectures {
+                    if let LectureKind::Assignment { reviewers } = lecture.kind {
+                        assert!(
+                            reviewers > 0 && reviewers as u32 <= T::MaxReviewers::get(),
+                            "genesis assignment has an invalid reviewer count"
+                        );
+                    }
+                    let lecture_id = <Pallet<T>>::next_lecture_id(&course_id)
+                        .expect("genesis lecture id is unique");
+                    <Lectures<T>>::insert(course_id, lecture_id, Lecture::<T> {
+                        name: lecture.name.clone(),
+                        contents: lecture.contents.clone(),
+                        owner: course.owner.clone(),
+                        kind: lecture.kind,
+                        release: None,
+                    });
+                }
+
+                if course.live {
+                    let bond = T::CourseBond::get();
+                    T::Currency::reserve(&course.owner, bond).expect("genesis course owner can pay the course bond");
+                    <CourseBonds<T>>::insert(&course_id, (course.owner.clone(), bond));
+                    <Courses<T>>::mutate(&course_id, |c| {
+                        if let Some(info) = c {
+                            info.live = true;
+                        }
+                    });
+                    <Pallet<T>>::snapshot_edition(&course_id).expect("genesis course edition fits its bounds");
+                }
+
+                for learner in &course.enrollments {
+                    let info = <Pallet<T>>::courses(&course_id).expect("genesis course was minted");
+                    <Pallet<T>>::ensure_can_enroll(learner, &course_id, &info)
+                        .expect("genesis learner can enroll in a published course");
+                    <Pallet<T>>::record_enrollment(learner, &course_id, Zero::zero());
+                }
+            }
+
+            for class in &self.classes {
+                let class_id = <Pallet<T>>::create_class(&class.owner, class.token_type)
+                    .expect("genesis class can be created");
+                for owner in &class.tokens {
+                    <Pallet<T>>::mint_token(class_id, owner).expect("genesis token can be minted");
+                }
+            }
+        }
+    }
+
+
+    #[pallet::hooks]
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        fn on_runtime_upgrade() -> Weight {
+            crate::migrations::v1::migrate::<T>()
+                .saturating_add(crate::migrations::v2::migrate::<T>())
+                .saturating_add(crate::migrations::v3::migrate::<T>())
+        }
+    }
+
+    #[pallet::call]
+    impl<T: Config> Pallet<T> {
+        /// Create a new unique course.
+        ///
+        /// The actual course creation is done in the `mint()` function.
+        #[pallet::weight(100)]
+        pub fn create_course(
+            origin: OriginFor<T>,
+            name: Vec<u8>,
+            category: Vec<u8>,
+            image_url: Vec<u8>,
+            description: Vec<u8>,
+        ) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+            let course_id = Self::mint(&sender, name, category, image_url, description)?;
+            log::info!("A course was created: {:?}.", course_id);
+            Self::deposit_event(Event::Created(sender, course_id));
+            Ok(())
+        }
+
+        /// Publish course
+        ///
+        /// Reserves `T::CourseBond` from the owner, which is slashed if the course is taken down.
+        #[transactional]
+        #[pallet::weight(Pallet::<T>::edition_weight())]
+        pub fn publish_course(
+            origin: OriginFor<T>,
+            course_id: T::Hash,
+        ) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+            let course = match Self::_get_course(&course_id, &sender) {
+                Ok(course) => course,
+                Err(err) => Err(err)?,
+            };
+            ensure!(Self::is_course_owner(&course_id, &sender)?, <Error<T>>::NotCourseOwner);
+            ensure!(!course.live, <Error<T>>::CourseAlreadyPublished);
+            ensure!(!<TakenDown<T>>::contains_key(&course_id), <Error<T>>::CourseTakenDown);
+            ensure!(
+                !T::RequireVerifiedInstructor::get() || Self::is_verified_instructor(&sender),
+                <Error<T>>::InstructorNotVerified
+            );
+
+            let bond = T::CourseBond::get();
+            T::Currency::reserve(&sender, bond).map_err(|_| <Error<T>>::NotEnoughBalance)?;
+            <CourseBonds<T>>::insert(&course_id, (sender.clone(), bond));
+
+            Courses::<T>::try_mutate_exists(course_id.clone(), |c| -> DispatchResult {
+                let mut info = c.as_mut().ok_or(Error::<T>::CourseNotExist)?;
+                info.live = true;
+                log::info!("A course is published: {:?}.", course_id);
+                // Generate event
+                Self::deposit_event(Event::Published(sender, course_id));
+                // Return a successful DispatchResult
+                Ok(())
+            })?;
+
+            // The first publication snapshots the lecture set as the first edition.
+            if Self::current_edition(&course_id) == 0 {
+                Self::snapshot_edition(&course_id)?;
+            }
+            Ok(())
+        }
+
+        /// Set lecture completed for a course.
+        ///
+        /// Pays the lecture reward, and the completion bonus once every lecture is completed,
+        /// when the course has a reward plan attached and the learner paid for the course.
+        /// Assignments can only be completed here once passed, when the completion could not
+        /// be recorded at grading time.
+        #[transactional]
+        #[pallet::weight(Pallet::<T>::completion_weight())]
+        pub fn complete_lecture(origin: OriginFor<T>, course_id: T::Hash, lecture_id: T::Hash) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+            let lecture = Self::lectures(&course_id, &lecture_id).ok_or(<Error<T>>::LectureNotExist)?;
+            let key = (course_id, lecture_id, sender.clone());
+            ensure!(
+                lecture.kind == LectureKind::Standard || <PassedAssignments<T>>::contains_key(&key),
+                <Error<T>>::LectureRequiresReview
+            );
+            Self::do_complete_lecture(&sender, &course_id, &lecture_id)?;
+            <PassedAssignments<T>>::remove(&key);
+            Ok(())
+        }
+
+        /// Add a lecture to a course.
+        ///
+        /// Lectures added to a published course can only be completed once they are part of an
+        /// edition published with `publish_edition`.
+        #[pallet::weight(100)]
+        pub fn create_lecture(origin: OriginFor<T>, course_id: T::Hash, name: Vec<u8>, contents: Vec<u8>) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+
+            // ACTION #1a: Checking Course owner
+            ensure!(Self::is_course_owner(&course_id, &sender)?, <Error<T>>::NotCourseOwner);
+            let lecture = Lecture::<T> {
+                name,
+                contents,
+                owner: sender.clone(),
+                kind: LectureKind::Standard,
+                release: None,
+            };
+            let lecture_id = Self::next_lecture_id(&course_id)?;
+
+            <Lectures<T>>::insert(course_id, lecture_id, lecture);
+            Ok(())
+        }
+
+        /// Update the name and contents of a lecture.
+        ///
+        /// Learners keep studying the edition they started until they migrate their progress to
+        /// a new edition published with `publish_edition`.
+        #[pallet::weight(100)]
+        pub fn update_lecture(origin: OriginFor<T>, course_id: T::Hash, lecture_id: T::Hash, name: Vec<u8>, contents: Vec<u8>) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+
+            ensure!(Self::is_course_owner(&course_id, &sender)?, <Error<T>>::NotCourseOwner);
+            // The lecture keeps its ID, editions detect the change through the content hash.
+            <Lectures<T>>::try_mutate(&course_id, &lecture_id, |l| -> DispatchResult {
+                let lecture = l.as_mut().ok_or(<Error<T>>::LectureNotExist)?;
+                lecture.name = name;
+                lecture.contents = contents;
+                Ok(())
+            })?;
+
+            Self::deposit_event(Event::LectureUpdated(sender, course_id, lecture_id));
+            Ok(())
+        }
+
+        /// Remove a lecture from the course.
+        #[pallet::weight(Pallet::<T>::lecture_removal_weight())]
+        pub fn remove_lecture(origin: OriginFor<T>, course_id: T::Hash, lecture_id: T::Hash) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+
+            // ACTION #1a: Checking Course owner
+            ensure!(Self::is_course_owner(&course_id, &sender)?, <Error<T>>::NotCourseOwner);
+            <Lectures<T>>::remove(course_id, lecture_id);
+            <LectureKeyCommitments<T>>::remove(course_id, lecture_id);
+            <LectureTranslations<T>>::remove_prefix((course_id, lecture_id), Some(T::MaxTranslations::get()));
+            <TranslationCount<T>>::remove(course_id, Some(lecture_id));
+            if let Some(section_id) = <LectureSections<T>>::take(course_id, lecture_id) {
+                <Sections<T>>::mutate(course_id, section_id, |s| {
+                    if let Some(section) = s {
+                        section.lectures.retain(|id| *id != lecture_id);
+                    }
+                });
+            }
+            Ok(())
+        }
+
+        /// Set the name for a Course.
+        ///
+        /// Updates Course name and updates storage.
+        #[pallet::weight(100)]
+        pub fn update_name(
+            origin: OriginFor<T>,
+            course_id: T::Hash,
+            new_name: Vec<u8>,
+        ) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+
+            // ACTION #1a: Checking Course owner
+            ensure!(Self::is_course_owner(&course_id, &sender)?, <Error<T>>::NotCourseOwner);
+
+            let mut course = Self::courses(&course_id).ok_or(<Error<T>>::CourseNotExist)?;
+
+            // ACTION #2: Set the Course price and update new Course infomation to storage.
+            course.name = new_name.clone();
+            <Courses<T>>::insert(&course_id, course);
+
+            // ACTION #3: Deposit a "NameSet" event.
+            // Deposit a "NameSet" event.
+            Self::deposit_event(Event::NameSet(sender, course_id, new_name));
+
+            Ok(())
+        }
+
+        /// Attach a learn-to-earn reward plan to a course.
+        ///
+        /// Can be called by the course owner or `T::RewardOrigin`. Once the reward pot holds
+        /// funds, the owner can only lower the rates of the existing plan.
+        #[pallet::weight(100)]
+        pub fn set_reward_plan(
+            origin: OriginFor<T>,
+            course_id: T::Hash,
+            per_lecture: BalanceOf<T>,
+            completion_bonus: BalanceOf<T>,
+            learner_cap: BalanceOf<T>,
+        ) -> DispatchResult {
+            let privileged = Self::ensure_owner_or_reward_origin(origin, &course_id)?;
+            let plan = RewardPlan::<T> {
+                per_lecture,
+                completion_bonus,
+                learner_cap,
+            };
+            if !privileged && !T::Currency::free_balance(&Self::reward_pot(&course_id)).is_zero() {
+                let current = Self::reward_plans(&course_id).ok_or(<Error<T>>::RewardPlanLocked)?;
+                ensure!(
+                    plan.per_lecture <= current.per_lecture
+                        && plan.completion_bonus <= current.completion_bonus
+                        && plan.learner_cap <= current.learner_cap,
+                    <Error<T>>::RewardPlanLocked
+                );
+            }
+            <RewardPlans<T>>::insert(&course_id, plan);
+            Self::deposit_event(Event::RewardPlanSet(course_id, per_lecture, completion_bonus, learner_cap));
+            Ok(())
+        }
+
+        /// Remove the reward plan of a course.
+        ///
+        /// Funds left in the reward pot stay there until a new plan is attached, or the pot
+        /// is swept with `sweep_rewards`.
+        #[pallet::weight(100)]
+        pub fn remove_reward_plan(origin: OriginFor<T>, course_id: T::Hash) -> DispatchResult {
+            Self::ensure_owner_or_reward_origin(origin, &course_id)?;
+            ensure!(<RewardPlans<T>>::contains_key(&course_id), <Error<T>>::RewardPlanNotExist);
+            <RewardPlans<T>>::remove(&course_id);
+            Self::deposit_event(Event::RewardPlanRemoved(course_id));
+            Ok(())
+        }
+
+        /// Top up the reward pot of a course.
+        ///
+        /// Treasury funding goes through a regular spend proposal whose beneficiary is the pot.
+        #[pallet::weight(100)]
+        pub fn fund_rewards(origin: OriginFor<T>, course_id: T::Hash, amount: BalanceOf<T>) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+            ensure!(<Courses<T>>::contains_key(&course_id), <Error<T>>::CourseNotExist);
+            T::Currency::transfer(&sender, &Self::reward_pot(&course_id), amount, ExistenceRequirement::KeepAlive)?;
+            Self::deposit_event(Event::RewardsFunded(sender, course_id, amount));
+            Ok(())
+        }
+
+        /// Return the funds left in the reward pot of a course to `T::Slashed`, the Treasury.
+        ///
+        /// Can be called by the course owner or `T::RewardOrigin` once the reward plan is removed.
+        #[pallet::weight(100)]
+        pub fn sweep_rewards(origin: OriginFor<T>, course_id: T::Hash) -> DispatchResult {
+            Self::ensure_owner_or_reward_origin(origin, &course_id)?;
+            ensure!(!<RewardPlans<T>>::contains_key(&course_id), <Error<T>>::RewardPlanActive);
+
+            let pot = Self::reward_pot(&course_id);
+            let amount = T::Currency::free_balance(&pot);
+            let imbalance = T::Currency::withdraw(
+                &pot, amount, WithdrawReasons::TRANSFER, ExistenceRequirement::AllowDeath,
+            )?;
+            T::Slashed::on_unbalanced(imbalance);
+
+            Self::deposit_event(Event::RewardsSwept(course_id, amount));
+            Ok(())
+        }
+
+        /// Unpublish a course and release the instructor bond.
+        ///
+        /// Not possible while complaints against the course are open. Complaints
+        /// `T::TakedownOrigin` did not act upon within `T::ComplaintPeriod` can be expired by
+        /// anyone with `expire_complaint`.
+        #[pallet::weight(100)]
+        pub fn unpublish_course(origin: OriginFor<T>, course_id: T::Hash) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+
+            ensure!(Self::is_course_owner(&course_id, &sender)?, <Error<T>>::NotCourseOwner);
+            ensure!(<Complaints<T>>::iter_prefix(&course_id).next().is_none(), <Error<T>>::ComplaintsPending);
+
+            let mut course = Self::courses(&course_id).ok_or(<Error<T>>::CourseNotExist)?;
+            ensure!(course.live, <Error<T>>::CourseNotLive);
+            course.live = false;
+            <Courses<T>>::insert(&course_id, course);
+
+            if let Some((depositor, bond)) = <CourseBonds<T>>::take(&course_id) {
+                T::Currency::unreserve(&depositor, bond);
+            }
+
+            Self::deposit_event(Event::Unpublished(sender, course_id));
+            Ok(())
+        }
+
+        /// File a complaint against a published course, backed by the hash of off-chain evidence.
+        ///
+        /// Reserves `T::ComplaintDeposit` from the reporter, refunded if the course is taken down.
+        #[transactional]
+        #[pallet::weight(100)]
+        pub fn file_complaint(origin: OriginFor<T>, course_id: T::Hash, evidence: T::Hash) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+
+            let course = Self::courses(&course_id).ok_or(<Error<T>>::CourseNotExist)?;
+            ensure!(course.live, <Error<T>>::CourseNotLive);
+            <OpenComplaints<T>>::try_mutate(&course_id, |count| -> DispatchResult {
+                ensure!(*count < T::MaxComplaints::get(), <Error<T>>::TooManyComplaints);
+                *count += 1;
+                Ok(())
+            })?;
+            let deposit = T::ComplaintDeposit::get();
+            T::Currency::reserve(&sender, deposit)?;
+
+            let complaint_id = <NextComplaintId<T>>::try_mutate(&course_id, |id| -> Result<u32, DispatchError> {
+                let current_id = *id;
+                *id = id.checked_add(1).ok_or(<Error<T>>::ComplaintCntOverflow)?;
+                Ok(current_id)
+            })?;
+            let complaint = Complaint::<T> {
+                reporter: sender.clone(),
+                evidence,
+                filed_at: <frame_system::Pallet<T>>::block_number(),
+                deposit,
+            };
+            <Complaints<T>>::insert(&course_id, complaint_id, complaint);
+
+            Self::deposit_event(Event::ComplaintFiled(sender, course_id, complaint_id, evidence));
+            Ok(())
+        }
+
+        /// Dismiss a complaint filed against a course and slash the reporter deposit.
+        ///
+        /// The dispatch origin for this call must be `T::TakedownOrigin`.
+        #[pallet::weight(100)]
+        pub fn dismiss_complaint(origin: OriginFor<T>, course_id: T::Hash, complaint_id: u32) -> DispatchResult {
+            T::TakedownOrigin::ensure_origin(origin)?;
+
+            let complaint = <Complaints<T>>::take(&course_id, complaint_id).ok_or(<Error<T>>::ComplaintNotExist)?;
+            <OpenComplaints<T>>::mutate(&course_id, |count| *count = count.saturating_sub(1));
+            let (imbalance, _) = T::Currency::slash_reserved(&complaint.reporter, complaint.deposit);
+            let slashed = imbalance.peek();
+            T::Slashed::on_unbalanced(imbalance);
+
+            Self::deposit_event(Event::ComplaintDismissed(course_id, complaint_id, slashed));
+            Ok(())
+        }
+
+        /// Close a complaint `T::TakedownOrigin` did not act upon within `T::ComplaintPeriod`
+        /// and refund the reporter deposit.
+        #[pallet::weight(100)]
+        pub fn expire_complaint(origin: OriginFor<T>, course_id: T::Hash, complaint_id: u32) -> DispatchResult {
+            ensure_signed(origin)?;
+
+            let complaint = Self::complaints(&course_id, complaint_id).ok_or(<Error<T>>::ComplaintNotExist)?;
+            let expiry = complaint.filed_at.saturating_add(T::ComplaintPeriod::get());
+            ensure!(<frame_system::Pallet<T>>::block_number() >= expiry, <Error<T>>::ComplaintPeriodNotElapsed);
+
+            <Complaints<T>>::remove(&course_id, complaint_id);
+            <OpenComplaints<T>>::mutate(&course_id, |count| *count = count.saturating_sub(1));
+            T::Currency::unreserve(&complaint.reporter, complaint.deposit);
+
+            Self::deposit_event(Event::ComplaintExpired(course_id, complaint_id));
+            Ok(())
+        }
+
+        /// Take an abusive course down, slash the instructor bond and refund the deposits of
+        /// the open complaints.
+        ///
+        /// The dispatch origin for this call must be `T::TakedownOrigin`, usually a motion of
+        /// the Council or the Technical Committee.
+        #[transactional]
+        #[pallet::weight(Pallet::<T>::takedown_weight())]
+        pub fn take_down_course(origin: OriginFor<T>, course_id: T::Hash) -> DispatchResult {
+            T::TakedownOrigin::ensure_origin(origin)?;
+
+            let mut course = Self::courses(&course_id).ok_or(<Error<T>>::CourseNotExist)?;
+            ensure!(!<TakenDown<T>>::contains_key(&course_id), <Error<T>>::CourseTakenDown);
+            course.live = false;
+            <Courses<T>>::insert(&course_id, course);
+            <TakenDown<T>>::insert(&course_id, <frame_system::Pallet<T>>::block_number());
+
+            let mut slashed = Zero::zero();
+            if let Some((depositor, bond)) = <CourseBonds<T>>::take(&course_id) {
+                let (imbalance, _) = T::Currency::slash_reserved(&depositor, bond);
+                slashed = imbalance.peek();
+                T::Slashed::on_unbalanced(imbalance);
+            }
+            for (_, complaint) in <Complaints<T>>::drain_prefix(&course_id) {
+                T::Currency::unreserve(&complaint.reporter, complaint.deposit);
+            }
+            <OpenComplaints<T>>::remove(&course_id);
+
+            Self::deposit_event(Event::TakenDown(course_id, slashed));
+            Ok(())
+        }
+
+        /// Create a cohort for a course.
+        #[pallet::weight(100)]
+        pub fn create_cohort(
+            origin: OriginFor<T>,
+            course_id: T::Hash,
+            start: MomentOf<T>,
+            end: MomentOf<T>,
+            max_seats: u32,
+        ) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+
+            ensure!(Self::is_course_owner(&course_id, &sender)?, <Error<T>>::NotCourseOwner);
+            ensure!(start < end, <Error<T>>::InvalidCohortSchedule);
+            ensure!(start >= T::Time::now(), <Error<T>>::CohortStartInPast);
+            ensure!(max_seats > 0, <Error<T>>::NoCohortSeats);
+
+            let cohort_id = <NextCohortId<T>>::try_mutate(&course_id, |id| -> Result<CohortId, DispatchError> {
+                let current_id = *id;
+                *id = id.checked_add(1).ok_or(<Error<T>>::CohortCntOverflow)?;
+                Ok(current_id)
+            })?;
+            let cohort = Cohort::<T> {
+                start,
+                end,
+                max_seats,
+                seats_taken: 0,
+                closed: false,
+            };
+            <Cohorts<T>>::insert(&course_id, cohort_id, cohort);
+
+            Self::deposit_event(Event::CohortCreated(course_id, cohort_id));
+            Ok(())
+        }
+
+        /// Set the due date of a lecture for a cohort.
+        #[pallet::weight(100)]
+        pub fn set_lecture_due_date(
+            origin: OriginFor<T>,
+            course_id: T::Hash,
+            cohort_id: CohortId,
+            lecture_id: T::Hash,
+            due: MomentOf<T>,
+        ) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+
+            ensure!(Self::is_course_owner(&course_id, &sender)?, <Error<T>>::NotCourseOwner);
+            let cohort = Self::cohorts(&course_id, cohort_id).ok_or(<Error<T>>::CohortNotExist)?;
+            ensure!(!cohort.closed, <Error<T>>::CohortClosed);
+            ensure!(<Lectures<T>>::contains_key(&course_id, &lecture_id), <Error<T>>::LectureNotExist);
+
+            <LectureDueDates<T>>::insert((course_id, cohort_id, lecture_id), due);
+
+            Self::deposit_event(Event::LectureDueDateSet(course_id, cohort_id, lecture_id, due));
+            Ok(())
+        }
+
+        /// Take a seat in a cohort of a course the sender has access to.
+        ///
+        /// Seats can only be taken until the cohort starts.
+        #[pallet::weight(100)]
+        pub fn join_cohort(origin: OriginFor<T>, course_id: T::Hash, cohort_id: CohortId) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+
+            let course = Self::courses(&course_id).ok_or(<Error<T>>::CourseNotExist)?;
+            ensure!(Self::has_access(&course, &course_id, &sender), <Error<T>>::NotEnrolled);
+            ensure!(!<CohortMembers<T>>::contains_key(&course_id, &sender), <Error<T>>::AlreadyInCohort);
+            <Cohorts<T>>::try_mutate(&course_id, cohort_id, |c| -> DispatchResult {
+                let cohort = c.as_mut().ok_or(<Error<T>>::CohortNotExist)?;
+                ensure!(!cohort.closed, <Error<T>>::CohortClosed);
+                ensure!(T::Time::now() < cohort.start, <Error<T>>::CohortStarted);
+                ensure!(cohort.seats_taken < cohort.max_seats, <Error<T>>::CohortFull);
+                cohort.seats_taken += 1;
+                Ok(())
+            })?;
+            <CohortMembers<T>>::insert(&course_id, &sender, cohort_id);
+
+            Self::deposit_event(Event::CohortJoined(sender, course_id, cohort_id));
+            Ok(())
+        }
+
+        /// Close a finished cohort, completions are no longer tracked for it.
+        #[pallet::weight(100)]
+        pub fn close_cohort(origin: OriginFor<T>, course_id: T::Hash, cohort_id: CohortId) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+
+            ensure!(Self::is_course_owner(&course_id, &sender)?, <Error<T>>::NotCourseOwner);
+            <Cohorts<T>>::try_mutate(&course_id, cohort_id, |c| -> DispatchResult {
+                let cohort = c.as_mut().ok_or(<Error<T>>::CohortNotExist)?;
+                ensure!(!cohort.closed, <Error<T>>::CohortClosed);
+                ensure!(T::Time::now() >= cohort.end, <Error<T>>::CohortNotEnded);
+                cohort.closed = true;
+                Ok(())
+            })?;
+
+            Self::deposit_event(Event::CohortClosed(course_id, cohort_id));
+            Ok(())
+        }
+
+        /// Give up the seat taken in a cohort of a course.
+        #[pallet::weight(100)]
+        pub fn leave_cohort(origin: OriginFor<T>, course_id: T::Hash) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+            Self::do_leave_cohort(&sender, &course_id)
+        }
+
+        /// Remove a learner from their cohort, freeing the seat.
+        #[pallet::weight(100)]
+        pub fn remove_cohort_member(origin: OriginFor<T>, course_id: T::Hash, learner: T::AccountId) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+
+            ensure!(Self::is_course_owner(&course_id, &sender)?, <Error<T>>::NotCourseOwner);
+            Self::do_leave_cohort(&learner, &course_id)
+        }
+
+        /// Volunteer to review the assignments of a course.
+        ///
+        /// Only accounts who completed the course, or were approved by its owner, can review.
+        #[pallet::weight(100)]
+        pub fn join_reviewer_pool(origin: OriginFor<T>, course_id: T::Hash) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+
+            ensure!(<Courses<T>>::contains_key(&course_id), <Error<T>>::CourseNotExist);
+            ensure!(
+                <CoursesCompleted<T>>::contains_key(&sender, &course_id)
+                    || <ApprovedReviewers<T>>::contains_key(&course_id, &sender),
+                <Error<T>>::NotQualifiedReviewer
+            );
+            <ReviewerPool<T>>::try_mutate(&course_id, |pool| -> DispatchResult {
+                ensure!(!pool.contains(&sender), <Error<T>>::AlreadyReviewer);
+                pool.try_push(sender.clone()).map_err(|_| <Error<T>>::ReviewerPoolFull)?;
+                Ok(())
+            })?;
+
+            Self::deposit_event(Event::ReviewerJoined(sender, course_id));
+            Ok(())
+        }
+
+        /// Leave the reviewer pool of a course.
+        ///
+        /// Submissions already assigned to the reviewer still await their score, until the learner
+        /// reassigns them once the review period elapsed.
+        #[pallet::weight(100)]
+        pub fn leave_reviewer_pool(origin: OriginFor<T>, course_id: T::Hash) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+
+            <ReviewerPool<T>>::try_mutate(&course_id, |pool| -> DispatchResult {
+                let ind = pool.iter().position(|r| *r == sender).ok_or(<Error<T>>::NotReviewer)?;
+                pool.swap_remove(ind);
+                Ok(())
+            })?;
+
+            Self::deposit_event(Event::ReviewerLeft(sender, course_id));
+            Ok(())
+        }
+
+        /// Allow an account to join the reviewer pool of a course without completing it.
+        #[pallet::weight(100)]
+        pub fn approve_reviewer(origin: OriginFor<T>, course_id: T::Hash, reviewer: T::AccountId) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+
+            ensure!(Self::is_course_owner(&course_id, &sender)?, <Error<T>>::NotCourseOwner);
+            <ApprovedReviewers<T>>::insert(&course_id, &reviewer, ());
+
+            Self::deposit_event(Event::ReviewerApproved(reviewer, course_id));
+            Ok(())
+        }
+
+        /// Submit the hash of an assignment's work and get peer reviewers assigned at random.
+        #[pallet::weight(Pallet::<T>::reviewer_assignment_weight())]
+        pub fn submit_assignment(
+            origin: OriginFor<T>,
+            course_id: T::Hash,
+            lecture_id: T::Hash,
+            content: T::Hash,
+        ) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+
+            let course = Self::courses(&course_id).ok_or(<Error<T>>::CourseNotExist)?;
+            ensure!(course.live, <Error<T>>::CourseNotPublished);
+            ensure!(Self::has_access(&course, &course_id, &sender), <Error<T>>::NotEnrolled);
+            let lecture = Self::lectures(&course_id, &lecture_id).ok_or(<Error<T>>::LectureNotExist)?;
+            ensure!(Self::is_released(&lecture, &course_id, Some(&sender)), <Error<T>>::LectureNotReleased);
+            let count = match lecture.kind {
+                LectureKind::Assignment { reviewers } => reviewers as usize,
+                LectureKind::Standard => Err(<Error<T>>::NotAnAssignment)?,
+            };
+            // Learners who did not start the course yet will study the current edition.
+            let edition = Self::learner_editions(&sender, &course_id)
+                .unwrap_or_else(|| Self::current_edition(&course_id));
+            ensure!(
+                Self::edition_lectures(&course_id, edition).iter().any(|(id, _)| *id == lecture_id),
+                <Error<T>>::LectureNotInEdition
+            );
+            ensure!(
+                !<LecturesCompleted<T>>::contains_key((sender.clone(), course_id, lecture_id)),
+                <Error<T>>::LectureAlreadyCompleted
+            );
+            ensure!(
+                !<Submissions<T>>::contains_key((course_id, lecture_id, sender.clone())),
+                <Error<T>>::SubmissionPending
+            );
+            ensure!(
+                !<PassedAssignments<T>>::contains_key((course_id, lecture_id, sender.clone())),
+                <Error<T>>::CompletionPending
+            );
+
+            let reviewers = Self::assign_reviewers(&sender, &course_id, count, &[])?;
+            let submission = Submission::<T> {
+                content,
+                reviewers: reviewers.clone(),
+                scores: Default::default(),
+                submitted_at: <frame_system::Pallet<T>>::block_number(),
+            };
+            <Submissions<T>>::insert((course_id, lecture_id, sender.clone()), submission);
+
+            Self::deposit_event(Event::AssignmentSubmitted(sender, course_id, lecture_id, reviewers.into_inner()));
+            Ok(())
+        }
+
+        /// Score an assignment submission, out of 100.
+        ///
+        /// Once every assigned reviewer scored it, the median score decides whether the
+        /// learner completes the lecture. The score is recorded even when the completion
+        /// cannot be, e.g. because the course was unpublished during the review; the learner
+        /// then completes the lecture later with `complete_lecture`.
+        #[transactional]
+        #[pallet::weight(Pallet::<T>::review_weight())]
+        pub fn review_assignment(
+            origin: OriginFor<T>,
+            course_id: T::Hash,
+            lecture_id: T::Hash,
+            learner: T::AccountId,
+            score: u8,
+        ) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+
+            ensure!(score <= 100, <Error<T>>::InvalidScore);
+            let key = (course_id, lecture_id, learner.clone());
+            let mut submission = Self::submissions(key.clone()).ok_or(<Error<T>>::SubmissionNotExist)?;
+            ensure!(submission.reviewers.contains(&sender), <Error<T>>::NotAssignedReviewer);
+            ensure!(!submission.scores.iter().any(|(r, _)| *r == sender), <Error<T>>::AlreadyReviewed);
+            submission.scores.try_push((sender.clone(), score)).map_err(|_| <Error<T>>::AlreadyReviewed)?;
+            Self::deposit_event(Event::AssignmentReviewed(sender, learner.clone(), course_id, lecture_id, score));
+
+            if submission.scores.len() < submission.reviewers.len() {
+                <Submissions<T>>::insert(key, submission);
+                return Ok(());
+            }
+
+            <Submissions<T>>::remove(key);
+            let median = Self::median_score(&submission.scores);
+            let tolerance = T::ReviewTolerance::get();
+            for (reviewer, score) in submission.scores.iter() {
+                <ReviewerReputation<T>>::mutate(reviewer, |reputation| {
+                    reputation.reviews = reputation.reviews.saturating_add(1);
+                    if (*score as i16 - median as i16).abs() <= tolerance as i16 {
+                        reputation.agreements = reputation.agreements.saturating_add(1);
+                    }
+                });
+            }
+
+            let passed = median >= T::PassingScore::get();
+            Self::deposit_event(Event::AssignmentGraded(learner.clone(), course_id, lecture_id, median, passed));
+            if passed {
+                let total = <CourseScores<T>>::mutate(&course_id, &learner, |total| {
+                    *total = total.saturating_add(median as u32);
+                    *total
+                });
+                Self::update_leaderboard(&course_id, LeaderboardKind::Score, &learner, total as u64);
+
+                let completed = with_transaction(|| match Self::do_complete_lecture(&learner, &course_id, &lecture_id) {
+                    Ok(()) => TransactionOutcome::Commit(true),
+                    Err(_) => TransactionOutcome::Rollback(false),
+                });
+                if !completed {
+                    <PassedAssignments<T>>::insert((course_id, lecture_id, learner.clone()), ());
+                    Self::deposit_event(Event::CompletionDeferred(learner, course_id, lecture_id));
+                }
+            }
+            Ok(())
+        }
+
+        /// Replace the reviewers who did not score a submission within `T::ReviewPeriod`.
+        ///
+        /// Scores already given are kept, and the review period starts over.
+        #[pallet::weight(Pallet::<T>::reviewer_assignment_weight())]
+        pub fn reassign_reviewers(origin: OriginFor<T>, course_id: T::Hash, lecture_id: T::Hash) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+
+            let key = (course_id, lecture_id, sender.clone());
+            let mut submission = Self::submissions(key.clone()).ok_or(<Error<T>>::SubmissionNotExist)?;
+            Self::ensure_review_period_elapsed(&submission)?;
+
+            let scored: Vec<T::AccountId> = submission.scores.iter().map(|(r, _)| r.clone()).collect();
+            let missing = submission.reviewers.len().saturating_sub(scored.len());
+            let fresh = Self::assign_reviewers(&sender, &course_id, missing, &submission.reviewers)?;
+            let mut reviewers = scored;
+            reviewers.extend(fresh.into_inner());
+            submission.reviewers = reviewers.clone().try_into().map_err(|_| <Error<T>>::InvalidReviewerCount)?;
+            submission.submitted_at = <frame_system::Pallet<T>>::block_number();
+            <Submissions<T>>::insert(key, submission);
+
+            Self::deposit_event(Event::ReviewersReassigned(sender, course_id, lecture_id, reviewers));
+            Ok(())
+        }
+
+        /// Withdraw a submission not graded within `T::ReviewPeriod`, so it can be submitted again.
+        #[pallet::weight(100)]
+        pub fn withdraw_submission(origin: OriginFor<T>, course_id: T::Hash, lecture_id: T::Hash) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+
+            let key = (course_id, lecture_id, sender.clone());
+            let submission = Self::submissions(key.clone()).ok_or(<Error<T>>::SubmissionNotExist)?;
+            Self::ensure_review_period_elapsed(&submission)?;
+            <Submissions<T>>::remove(key);
+
+            Self::deposit_event(Event::SubmissionWithdrawn(sender, course_id, lecture_id));
+            Ok(())
+        }
+
+        /// Add a peer-reviewed assignment to a course.
+        #[pallet::weight(100)]
+        pub fn create_assignment(
+            origin: OriginFor<T>,
+            course_id: T::Hash,
+            name: Vec<u8>,
+            contents: Vec<u8>,
+            reviewers: u8,
+        ) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+
+            ensure!(Self::is_course_owner(&course_id, &sender)?, <Error<T>>::NotCourseOwner);
+            ensure!(
+                reviewers > 0 && reviewers as u32 <= T::MaxReviewers::get(),
+                <Error<T>>::InvalidReviewerCount
+            );
+            let lecture = Lecture::<T> {
+                name,
+                contents,
+                owner: sender.clone(),
+                kind: LectureKind::Assignment { reviewers },
+                release: None,
+            };
+            let lecture_id = Self::next_lecture_id(&course_id)?;
+
+            <Lectures<T>>::insert(course_id, lecture_id, lecture);
+            Ok(())
+        }
+
+        /// Set the price for a Course, in the native token or in an asset.
+        ///
+        /// A course without price is free to follow without enrolling.
+        #[pallet::weight(100)]
+        pub fn set_price(
+            origin: OriginFor<T>,
+            course_id: T::Hash,
+            new_price: Option<BalanceOf<T>>,
+            currency: PaymentCurrency<T::AssetId>,
+        ) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+
+            ensure!(Self::is_course_owner(&course_id, &sender)?, <Error<T>>::NotCourseOwner);
+            // `pallet_assets` reports a zero minimum balance for assets that were never created.
+            if let PaymentCurrency::Asset(asset) = currency {
+                ensure!(
+                    !<T::Assets as fungibles::Inspect<T::AccountId>>::minimum_balance(asset).is_zero(),
+                    <Error<T>>::AssetNotExist
+                );
+            }
+
+            let mut course = Self::courses(&course_id).ok_or(<Error<T>>::CourseNotExist)?;
+            course.price = new_price.clone();
+            course.currency = currency;
+            <Courses<T>>::insert(&course_id, course);
+
+            Self::deposit_event(Event::PriceSet(sender, course_id, new_price, currency));
+            Ok(())
+        }
+
+        /// Enroll in a published course, paying its price to the course owner.
+        ///
+        /// The price can be discounted by redeeming the coupon with the given code. When a
+        /// `referrer` is given, the referral commission of the course is paid to them from the sale.
+        #[transactional]
+        #[pallet::weight(Pallet::<T>::enrollment_weight())]
+        pub fn enroll(
+            origin: OriginFor<T>,
+            course_id: T::Hash,
+            coupon: Option<T::Hash>,
+            referrer: Option<T::AccountId>,
+        ) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+
+            let price = Self::enrollment_price(&sender, &course_id, coupon)?;
+            let course = Self::courses(&course_id).ok_or(<Error<T>>::CourseNotExist)?;
+            Self::ensure_can_enroll(&sender, &course_id, &course)?;
+            if let Some(referrer) = &referrer {
+                ensure!(*referrer != sender && *referrer != course.owner, <Error<T>>::InvalidReferrer);
+            }
+            Self::pay_for_course(&sender, &course, &course_id, price, ExistenceRequirement::KeepAlive, referrer.as_ref())?;
+            Self::record_enrollment(&sender, &course_id, price);
+            Ok(())
+        }
+
+        /// Bundle courses owned by the sender into a learning path.
+        ///
+        /// A certificate NFT class is created for the path, a token of it is issued to every
+        /// learner completing all the courses of the path.
+        #[pallet::weight(Pallet::<T>::path_creation_weight(courses.len()))]
+        pub fn create_learning_path(
+            origin: OriginFor<T>,
+            name: Vec<u8>,
+            courses: Vec<T::Hash>,
+            discount: Percent,
+        ) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+
+            ensure!(!courses.is_empty(), <Error<T>>::InvalidPathCourses);
+            let courses: BoundedVec<T::Hash, T::MaxPathCourses> =
+                courses.try_into().map_err(|_| <Error<T>>::InvalidPathCourses)?;
+            for (ind, course_id) in courses.iter().enumerate() {
+                ensure!(Self::is_course_owner(course_id, &sender)?, <Error<T>>::NotCourseOwner);
+                ensure!(!courses[..ind].contains(course_id), <Error<T>>::DuplicatePathCourse);
+            }
+
+            let path_id = <NextPathId<T>>::try_mutate(|id| -> Result<PathId, DispatchError> {
+                let current_id = *id;
+                *id = id.checked_add(1).ok_or(<Error<T>>::LearningPathCntOverflow)?;
+                Ok(current_id)
+            })?;
+            let certificate_class = Self::create_class(&sender, TokenType::BoundToAddress)?;
+            let path = LearningPath::<T> {
+                name,
+                owner: sender.clone(),
+                courses,
+                discount,
+                certificate_class,
+            };
+            <LearningPaths<T>>::insert(path_id, path);
+
+            Self::deposit_event(Event::LearningPathCreated(sender, path_id, certificate_class));
+            Ok(())
+        }
+
+        /// Enroll in every course of a learning path at the discounted bundle price.
+        ///
+        /// Courses the sender is already enrolled in are neither charged nor enrolled again. The
+        /// courses charged must all be priced in the same currency.
+        #[transactional]
+        #[pallet::weight(Pallet::<T>::path_enrollment_weight())]
+        pub fn enroll_in_path(origin: OriginFor<T>, path_id: PathId) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+
+            let path = Self::learning_paths(path_id).ok_or(<Error<T>>::LearningPathNotExist)?;
+            ensure!(!<PathEnrollments<T>>::contains_key(&sender, path_id), <Error<T>>::AlreadyEnrolledInPath);
+            <LearnerPathCount<T>>::try_mutate(&sender, |count| -> DispatchResult {
+                ensure!(*count < T::MaxLearnerPaths::get(), <Error<T>>::TooManyPaths);
+                *count += 1;
+                Ok(())
+            })?;
+
+            let mut currency: Option<PaymentCurrency<T::AssetId>> = None;
+            let mut paid: BalanceOf<T> = Zero::zero();
+            for course_id in path.courses.iter() {
+                if <Enrollments<T>>::contains_key(&sender, course_id) {
+                    continue;
+                }
+                let course = Self::courses(course_id).ok_or(<Error<T>>::CourseNotExist)?;
+                let price = course.price.unwrap_or_else(Zero::zero);
+                let price = price.saturating_sub(path.discount * price);
+                if !price.is_zero() {
+                    ensure!(currency.map_or(true, |c| c == course.currency), <Error<T>>::MixedCurrencyPath);
+                    currency = Some(course.currency);
+                }
+                Self::do_enroll(&sender, &sender, course_id, price)?;
+                paid = paid.saturating_add(price);
+            }
+            <PathEnrollments<T>>::insert(&sender, path_id, <frame_system::Pallet<T>>::block_number());
+
+            let currency = currency.unwrap_or_default();
+            Self::deposit_event(Event::PathEnrolled(sender.clone(), path_id, currency, paid));
+            // Learners may have completed every course of the path before enrolling in it.
+            Self::try_issue_path_certificate(&sender, path_id, &path)?;
+            Ok(())
+        }
+
+        /// Create a discount coupon for the courses of the sender.
+        ///
+        /// `code` is the hash of the code handed out to learners.
+        #[pallet::weight(100)]
+        pub fn create_coupon(
+            origin: OriginFor<T>,
+            code: T::Hash,
+            discount: Discount<BalanceOf<T>>,
+            max_redemptions: u32,
+            expires_at: T::BlockNumber,
+            course: Option<T::Hash>,
+        ) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+
+            ensure!(!<Coupons<T>>::contains_key(&code), <Error<T>>::CouponAlreadyExists);
+            ensure!(
+                max_redemptions > 0 && max_redemptions <= T::MaxCouponRedemptions::get(),
+                <Error<T>>::InvalidRedemptionLimit
+            );
+            ensure!(expires_at > <frame_system::Pallet<T>>::block_number(), <Error<T>>::CouponExpired);
+            if let Some(course_id) = &course {
+                ensure!(Self::is_course_owner(course_id, &sender)?, <Error<T>>::NotCourseOwner);
+            }
+
+            let coupon = Coupon::<T> {
+                owner: sender.clone(),
+                discount,
+                max_redemptions,
+                redemptions: 0,
+                expires_at,
+                course,
+            };
+            <Coupons<T>>::insert(&code, coupon);
+
+            Self::deposit_event(Event::CouponCreated(sender, code));
+            Ok(())
+        }
+
+        /// Revoke a coupon before it expires.
+        #[pallet::weight(Pallet::<T>::coupon_revocation_weight())]
+        pub fn revoke_coupon(origin: OriginFor<T>, code: T::Hash) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+
+            let coupon = Self::coupons(&code).ok_or(<Error<T>>::CouponNotExist)?;
+            ensure!(coupon.owner == sender, <Error<T>>::NotCouponOwner);
+            <Coupons<T>>::remove(&code);
+            let _ = <CouponRedemptions<T>>::remove_prefix(&code, None);
+
+            Self::deposit_event(Event::CouponRevoked(sender, code));
+            Ok(())
+        }
+
+        /// Enroll another account in a course, paid by the sender.
+        ///
+        /// With `claimable`, the payment is held in escrow until the recipient accepts the gift.
+        #[transactional]
+        #[pallet::weight(Pallet::<T>::enrollment_weight())]
+        pub fn gift_enrollment(
+            origin: OriginFor<T>,
+            course_id: T::Hash,
+            recipient: T::AccountId,
+            coupon: Option<T::Hash>,
+            claimable: bool,
+        ) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+
+            ensure!(sender != recipient, <Error<T>>::GiftToSelf);
+            let price = Self::enrollment_price(&sender, &course_id, coupon)?;
+            if !claimable {
+                Self::do_enroll(&sender, &recipient, &course_id, price)?;
+                Self::deposit_event(Event::EnrollmentGifted(sender, recipient, course_id, price, true));
+                return Ok(());
+            }
+
+            let course = Self::courses(&course_id).ok_or(<Error<T>>::CourseNotExist)?;
+            Self::ensure_can_enroll(&recipient, &course_id, &course)?;
+            ensure!(!<PendingGifts<T>>::contains_key(&recipient, &course_id), <Error<T>>::GiftAlreadyPending);
+            if !price.is_zero() {
+                Self::transfer_funds(&course.currency, &sender, &Self::gift_escrow(), price, ExistenceRequirement::KeepAlive)?;
+            }
+            let gift = Gift::<T> {
+                payer: sender.clone(),
+                amount: price,
+                currency: course.currency,
+                offered_at: <frame_system::Pallet<T>>::block_number(),
+                coupon,
+            };
+            <PendingGifts<T>>::insert(&recipient, &course_id, gift);
+
+            Self::deposit_event(Event::EnrollmentGifted(sender, recipient, course_id, price, false));
+            Ok(())
+        }
+
+        /// Accept a gifted enrollment, releasing the escrowed payment to the course owner.
+        #[transactional]
+        #[pallet::weight(Pallet::<T>::enrollment_weight())]
+        pub fn accept_gift(origin: OriginFor<T>, course_id: T::Hash) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+
+            let gift = <PendingGifts<T>>::take(&sender, &course_id).ok_or(<Error<T>>::GiftNotExist)?;
+            let mut course = Self::courses(&course_id).ok_or(<Error<T>>::CourseNotExist)?;
+            Self::ensure_can_enroll(&sender, &course_id, &course)?;
+            // Settle in the currency the gift was paid in, even if the course price changed since.
+            course.currency = gift.currency;
+            Self::pay_for_course(&Self::gift_escrow(), &course, &course_id, gift.amount, ExistenceRequirement::AllowDeath, None)?;
+            Self::record_enrollment(&sender, &course_id, gift.amount);
+
+            Self::deposit_event(Event::GiftAccepted(gift.payer, sender, course_id));
+            Ok(())
+        }
+
+        /// Cancel a pending gift, refund the payer and restore the coupon redemption.
+        ///
+        /// Can be called by either the payer or the recipient of the gift.
+        #[transactional]
         #[pallet::weight(100)]
-        pub fn create_course(
+        pub fn cancel_gift(origin: OriginFor<T>, course_id: T::Hash, recipient: T::AccountId) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+
+            let gift = Self::pending_gifts(&recipient, &course_id).ok_or(<Error<T>>::GiftNotExist)?;
+            ensure!(sender == gift.payer || sender == recipient, <Error<T>>::NotGiftParty);
+            <PendingGifts<T>>::remove(&recipient, &course_id);
+            if !gift.amount.is_zero() {
+                Self::transfer_funds(&gift.currency, &Self::gift_escrow(), &gift.payer, gift.amount, ExistenceRequirement::AllowDeath)?;
+            }
+            if let Some(code) = gift.coupon {
+                <Coupons<T>>::mutate(&code, |c| {
+                    if let Some(coupon) = c {
+                        coupon.redemptions = coupon.redemptions.saturating_sub(1);
+                    }
+                });
+                <CouponRedemptions<T>>::remove(&code, &gift.payer);
+            }
+
+            Self::deposit_event(Event::GiftCancelled(gift.payer, recipient, course_id));
+            Ok(())
+        }
+
+        /// Set the prerequisites and enrollment capacity of a course.
+        #[pallet::weight(Pallet::<T>::enrollment_rules_weight(prerequisites.len()))]
+        pub fn set_enrollment_rules(
+            origin: OriginFor<T>,
+            course_id: T::Hash,
+            prerequisites: Vec<T::Hash>,
+            capacity: Option<u32>,
+        ) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+
+            ensure!(Self::is_course_owner(&course_id, &sender)?, <Error<T>>::NotCourseOwner);
+            ensure!(!prerequisites.contains(&course_id), <Error<T>>::InvalidPrerequisites);
+            let prerequisites: BoundedVec<T::Hash, T::MaxPrerequisites> =
+                prerequisites.try_into().map_err(|_| <Error<T>>::InvalidPrerequisites)?;
+            for prerequisite in prerequisites.iter() {
+                ensure!(<Courses<T>>::contains_key(prerequisite), <Error<T>>::CourseNotExist);
+            }
+            let rules = EnrollmentRules::<T> {
+                prerequisites,
+                capacity,
+            };
+            <CourseEnrollmentRules<T>>::insert(&course_id, rules);
+
+            Self::deposit_event(Event::EnrollmentRulesSet(course_id));
+            Ok(())
+        }
+
+        /// Set when a lecture unlocks, either at a fixed block or relative to each enrollment.
+        #[pallet::weight(100)]
+        pub fn set_lecture_release(
+            origin: OriginFor<T>,
+            course_id: T::Hash,
+            lecture_id: T::Hash,
+            release: Option<ReleaseSchedule<T::BlockNumber>>,
+        ) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+
+            ensure!(Self::is_course_owner(&course_id, &sender)?, <Error<T>>::NotCourseOwner);
+            <Lectures<T>>::try_mutate(&course_id, &lecture_id, |l| -> DispatchResult {
+                let lecture = l.as_mut().ok_or(<Error<T>>::LectureNotExist)?;
+                lecture.release = release;
+                Ok(())
+            })?;
+
+            Self::deposit_event(Event::LectureReleaseSet(course_id, lecture_id, release));
+            Ok(())
+        }
+
+        /// Publish a new edition of a live course, snapshotting its current lecture set.
+        ///
+        /// Learners keep studying the edition they started until they migrate their progress.
+        #[pallet::weight(Pallet::<T>::edition_weight())]
+        pub fn publish_edition(origin: OriginFor<T>, course_id: T::Hash) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+
+            ensure!(Self::is_course_owner(&course_id, &sender)?, <Error<T>>::NotCourseOwner);
+            let course = Self::courses(&course_id).ok_or(<Error<T>>::CourseNotExist)?;
+            ensure!(course.live, <Error<T>>::CourseNotLive);
+            Self::snapshot_edition(&course_id)?;
+            Ok(())
+        }
+
+        /// Move the sender's progress to the current edition of a course.
+        ///
+        /// Completions of lectures left unchanged between both editions are kept, the others
+        /// are dropped and must be completed again. The course is completed right away when
+        /// every lecture of the current edition was kept.
+        #[transactional]
+        #[pallet::weight(Pallet::<T>::migration_weight())]
+        pub fn migrate_progress(origin: OriginFor<T>, course_id: T::Hash) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+
+            let course = Self::courses(&course_id).ok_or(<Error<T>>::CourseNotExist)?;
+            let current = Self::current_edition(&course_id);
+            let from = Self::learner_editions(&sender, &course_id).ok_or(<Error<T>>::CourseNotStarted)?;
+            ensure!(from < current, <Error<T>>::AlreadyOnCurrentEdition);
+            let old_lectures = Self::edition_lectures(&course_id, from);
+            let new_lectures = Self::edition_lectures(&course_id, current);
+
+            let mut kept = 0u32;
+            let completed: Vec<T::Hash> = <LecturesCompleted<T>>::iter_key_prefix((sender.clone(), course_id)).collect();
+            for lecture_id in completed {
+                let key = (sender.clone(), course_id, lecture_id);
+                let unchanged = old_lectures.iter()
+                    .find(|(id, _)| *id == lecture_id)
+                    .map_or(false, |old| new_lectures.contains(old));
+                if unchanged {
+                    <LecturesCompleted<T>>::mutate(key, |c| {
+                        if let Some(completion) = c {
+                            completion.edition = current;
+                        }
+                    });
+                    kept += 1;
+                } else {
+                    <LecturesCompleted<T>>::remove(key);
+                    <LectureCompletionCount<T>>::mutate(&course_id, &lecture_id, |count| {
+                        *count = count.saturating_sub(1)
+                    });
+                    <CourseLectureCount<T>>::mutate(&sender, &course_id, |count| *count = count.saturating_sub(1));
+                }
+            }
+            <LearnerEditions<T>>::insert(&sender, &course_id, current);
+            Self::deposit_event(Event::ProgressMigrated(sender.clone(), course_id, from, current, kept));
+
+            if Self::try_complete_course(&sender, &course_id, &course, current, &new_lectures)? {
+                Self::evaluate_badges(&sender, &course_id, &course, true)?;
+            }
+            Ok(())
+        }
+
+        /// Set the share of each sale paid to the referrer of an enrollment.
+        #[pallet::weight(100)]
+        pub fn set_referral_commission(
+            origin: OriginFor<T>,
+            course_id: T::Hash,
+            commission: Perbill,
+        ) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+
+            ensure!(Self::is_course_owner(&course_id, &sender)?, <Error<T>>::NotCourseOwner);
+            <ReferralCommissions<T>>::insert(&course_id, commission);
+
+            Self::deposit_event(Event::ReferralCommissionSet(sender, course_id, commission));
+            Ok(())
+        }
+
+        /// Add a lecture whose contents are encrypted with a content key.
+        ///
+        /// `key_commitment` is the hash of the content key, letting learners check the key
+        /// granted to them.
+        #[pallet::weight(100)]
+        pub fn create_encrypted_lecture(
+            origin: OriginFor<T>,
+            course_id: T::Hash,
+            name: Vec<u8>,
+            ciphertext: Vec<u8>,
+            key_commitment: T::Hash,
+        ) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+
+            ensure!(Self::is_course_owner(&course_id, &sender)?, <Error<T>>::NotCourseOwner);
+            let lecture = Lecture::<T> {
+                name,
+                contents: ciphertext,
+                owner: sender.clone(),
+                kind: LectureKind::Standard,
+                release: None,
+            };
+            let lecture_id = Self::next_lecture_id(&course_id)?;
+
+            <Lectures<T>>::insert(course_id, lecture_id, lecture);
+            <LectureKeyCommitments<T>>::insert(course_id, lecture_id, key_commitment);
+
+            Self::deposit_event(Event::EncryptedLectureCreated(sender, course_id, lecture_id, key_commitment));
+            Ok(())
+        }
+
+        /// Register the public key content keys of encrypted lectures are wrapped to.
+        #[pallet::weight(100)]
+        pub fn register_encryption_key(origin: OriginFor<T>, public_key: [u8; 32]) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+
+            <EncryptionKeys<T>>::insert(&sender, public_key);
+
+            Self::deposit_event(Event::EncryptionKeyRegistered(sender, public_key));
+            Ok(())
+        }
+
+        /// Grant an enrolled learner the content key of an encrypted lecture, wrapped to the
+        /// encryption key they registered.
+        ///
+        /// The pallet cannot check the wrapping, but the grant is the on-chain record of who had
+        /// access to the lecture.
+        #[pallet::weight(100)]
+        pub fn grant_lecture_key(
+            origin: OriginFor<T>,
+            course_id: T::Hash,
+            lecture_id: T::Hash,
+            learner: T::AccountId,
+            wrapped_key: Vec<u8>,
+        ) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+
+            ensure!(Self::is_course_owner(&course_id, &sender)?, <Error<T>>::NotCourseOwner);
+            ensure!(
+                <LectureKeyCommitments<T>>::contains_key(&course_id, &lecture_id),
+                <Error<T>>::LectureNotEncrypted
+            );
+            ensure!(<Enrollments<T>>::contains_key(&learner, &course_id), <Error<T>>::NotEnrolled);
+            ensure!(<EncryptionKeys<T>>::contains_key(&learner), <Error<T>>::EncryptionKeyNotRegistered);
+
+            let wrapped = WrappedKey::<T> {
+                key: wrapped_key.try_into().map_err(|_| <Error<T>>::WrappedKeyTooLong)?,
+                granted_at: <frame_system::Pallet<T>>::block_number(),
+            };
+            <WrappedKeys<T>>::insert((course_id, lecture_id, learner.clone()), wrapped);
+
+            Self::deposit_event(Event::LectureKeyGranted(course_id, lecture_id, learner));
+            Ok(())
+        }
+
+        /// Create a new course together with its sections of lectures, in a single transaction.
+        ///
+        /// Either the course and every lecture are created, or nothing is.
+        #[transactional]
+        #[pallet::weight(Pallet::<T>::bulk_weight(sections))]
+        pub fn create_course_with_lectures(
             origin: OriginFor<T>,
             name: Vec<u8>,
             category: Vec<u8>,
             image_url: Vec<u8>,
             description: Vec<u8>,
+            sections: Vec<SectionInput>,
         ) -> DispatchResult {
             let sender = ensure_signed(origin)?;
             let course_id = Self::mint(&sender, name, category, image_url, description)?;
             log::info!("A course was created: {:?}.", course_id);
-            Self::deposit_event(Event::Created(sender, course_id));
+            Self::deposit_event(Event::Created(sender.clone(), course_id));
+
+            Self::add_sections(&sender, &course_id, sections)
+        }
+
+        /// Add sections of lectures to an existing course, in a single transaction.
+        #[transactional]
+        #[pallet::weight(Pallet::<T>::bulk_weight(sections))]
+        pub fn import_lectures(
+            origin: OriginFor<T>,
+            course_id: T::Hash,
+            sections: Vec<SectionInput>,
+        ) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+
+            ensure!(Self::is_course_owner(&course_id, &sender)?, <Error<T>>::NotCourseOwner);
+            Self::add_sections(&sender, &course_id, sections)
+        }
+
+        /// Allow an account to manage the translations of a course.
+        #[pallet::weight(100)]
+        pub fn add_translator(origin: OriginFor<T>, course_id: T::Hash, translator: T::AccountId) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+
+            ensure!(Self::is_course_owner(&course_id, &sender)?, <Error<T>>::NotCourseOwner);
+            ensure!(!<Translators<T>>::contains_key(&course_id, &translator), <Error<T>>::AlreadyTranslator);
+            <Translators<T>>::insert(&course_id, &translator, ());
+
+            Self::deposit_event(Event::TranslatorAdded(course_id, translator));
             Ok(())
         }
 
-        /// Publish course
+        /// Revoke the translator role of an account. Existing translations are kept.
         #[pallet::weight(100)]
-        pub fn publish_course(
+        pub fn remove_translator(origin: OriginFor<T>, course_id: T::Hash, translator: T::AccountId) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+
+            ensure!(Self::is_course_owner(&course_id, &sender)?, <Error<T>>::NotCourseOwner);
+            ensure!(<Translators<T>>::contains_key(&course_id, &translator), <Error<T>>::NotTranslator);
+            <Translators<T>>::remove(&course_id, &translator);
+
+            Self::deposit_event(Event::TranslatorRemoved(course_id, translator));
+            Ok(())
+        }
+
+        /// Set the name and description of a course in another language.
+        #[pallet::weight(100)]
+        pub fn set_course_translation(
             origin: OriginFor<T>,
             course_id: T::Hash,
+            language: Vec<u8>,
+            name: Vec<u8>,
+            description: Vec<u8>,
         ) -> DispatchResult {
             let sender = ensure_signed(origin)?;
-            let course = match Self::_get_course(&course_id, &sender) {
-                Ok(course) => course,
-                Err(err) => Err(err)?,
-            };
-            ensure!(Self::is_course_owner(&course_id, &sender)?, <Error<T>>::NotCourseOwner);
-            ensure!(!course.live, <Error<T>>::CourseAlreadyPublished);
-            Courses::<T>::try_mutate_exists(course_id.clone(), |c| {
-                let mut info = c.as_mut().ok_or(Error::<T>::CourseNotExist)?;
-                info.live = true;
-                log::info!("A course is published: {:?}.", course_id);
-                // Generate event
-                Self::deposit_event(Event::Published(sender, course_id));
-                // Return a successful DispatchResult
-                Ok(())
-            })
+
+            Self::ensure_translator(&course_id, &sender)?;
+            let tag = Self::language_tag(&language)?;
+            if !<CourseTranslations<T>>::contains_key(&course_id, &tag) {
+                Self::count_translation(&course_id, None)?;
+            }
+            <CourseTranslations<T>>::insert(&course_id, tag, Translation { name, body: description });
+
+            Self::deposit_event(Event::CourseTranslationSet(sender, course_id, language));
+            Ok(())
         }
 
+        /// Set the name and contents of a lecture in another language.
+        ///
+        /// Encrypted lectures cannot be translated.
+        #[pallet::weight(100)]
+        pub fn set_lecture_translation(
+            origin: OriginFor<T>,
+            course_id: T::Hash,
+            lecture_id: T::Hash,
+            language: Vec<u8>,
+            name: Vec<u8>,
+            contents: Vec<u8>,
+        ) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+
+            Self::ensure_translator(&course_id, &sender)?;
+            ensure!(<Lectures<T>>::contains_key(&course_id, &lecture_id), <Error<T>>::LectureNotExist);
+            ensure!(
+                !<LectureKeyCommitments<T>>::contains_key(&course_id, &lecture_id),
+                <Error<T>>::LectureEncrypted
+            );
+            let tag = Self::language_tag(&language)?;
+            if !<LectureTranslations<T>>::contains_key((course_id, lecture_id, tag.clone())) {
+                Self::count_translation(&course_id, Some(lecture_id))?;
+            }
+            <LectureTranslations<T>>::insert((course_id, lecture_id, tag), Translation { name, body: contents });
 
-        /// Set lecture completed for a course.
+            Self::deposit_event(Event::LectureTranslationSet(sender, course_id, lecture_id, language));
+            Ok(())
+        }
+
+        /// Remove the translation of a course, or of one of its lectures if `lecture_id` is given.
         #[pallet::weight(100)]
-        pub fn complete_lecture(origin: OriginFor<T>, course_id: T::Hash, lecture_id: T::Hash) -> DispatchResult {
-            let sender = ensure_signed(origin.clone())?;
-            let lecture_completed = LectureCompleted::<T> {
-                owner: sender.clone(),
-            };
-            if Self::_random_number(&sender.clone()) < 1 {
-                Self::generate_nft(&sender.clone());
+        pub fn remove_translation(
+            origin: OriginFor<T>,
+            course_id: T::Hash,
+            lecture_id: Option<T::Hash>,
+            language: Vec<u8>,
+        ) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+
+            Self::ensure_translator(&course_id, &sender)?;
+            let tag = Self::language_tag(&language)?;
+            match lecture_id {
+                Some(lecture_id) => {
+                    <LectureTranslations<T>>::take((course_id, lecture_id, tag))
+                        .ok_or(<Error<T>>::TranslationNotExist)?;
+                }
+                None => {
+                    <CourseTranslations<T>>::take(&course_id, tag).ok_or(<Error<T>>::TranslationNotExist)?;
+                }
             }
-            <LecturesCompleted<T>>::insert((sender.clone(), course_id, lecture_id), lecture_completed);
+            <TranslationCount<T>>::mutate(&course_id, lecture_id, |count| *count = count.saturating_sub(1));
+
+            Self::deposit_event(Event::TranslationRemoved(sender, course_id, lecture_id, language));
             Ok(())
         }
 
-        /// Add a lecture to a course.
+        /// Set the license a course is published under.
         #[pallet::weight(100)]
-        pub fn create_lecture(origin: OriginFor<T>, course_id: T::Hash, name: Vec<u8>, contents: Vec<u8>) -> DispatchResult {
+        pub fn set_license(origin: OriginFor<T>, course_id: T::Hash, license: License) -> DispatchResult {
             let sender = ensure_signed(origin)?;
 
-            // ACTION #1a: Checking Course owner
             ensure!(Self::is_course_owner(&course_id, &sender)?, <Error<T>>::NotCourseOwner);
-            let lecture = Lecture::<T> {
-                name,
-                contents,
-                owner: sender.clone(),
+            let mut course = Self::courses(&course_id).ok_or(<Error<T>>::CourseNotExist)?;
+            if let Some(parent) = course.parent.and_then(|p| Self::courses(&p)) {
+                ensure!(
+                    parent.license != License::CcBySa || license == License::CcBySa,
+                    <Error<T>>::ShareAlikeRequired
+                );
+            }
+            course.license = license;
+            <Courses<T>>::insert(&course_id, course);
+
+            Self::deposit_event(Event::LicenseSet(sender, course_id, license));
+            Ok(())
+        }
+
+        /// Fork a course whose license allows derivatives, copying its metadata and lectures.
+        ///
+        /// Encrypted lectures are not copied. The derivative starts unpublished and references its
+        /// parent, whose owner earns royalties on its sales when it was forked under
+        /// `CommercialWithRoyalty`, at the rate in force at fork time.
+        #[transactional]
+        #[pallet::weight(Pallet::<T>::fork_weight())]
+        pub fn fork_course(origin: OriginFor<T>, parent_id: T::Hash) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+
+            let parent = Self::courses(&parent_id).ok_or(<Error<T>>::CourseNotExist)?;
+            ensure!(!<TakenDown<T>>::contains_key(&parent_id), <Error<T>>::CourseTakenDown);
+            ensure!(
+                parent.owner == sender || parent.license != License::AllRightsReserved,
+                <Error<T>>::LicenseForbidsForking
+            );
+            // Reading one lecture past the bound is enough to reject oversized courses.
+            let lectures: Vec<(T::Hash, Lecture<T>)> = <Lectures<T>>::iter_prefix(&parent_id)
+                .take(T::MaxEditionLectures::get().saturating_add(1) as usize)
+                .collect();
+            ensure!(lectures.len() <= T::MaxEditionLectures::get() as usize, <Error<T>>::TooManyLectures);
+            let lectures = lectures.into_iter()
+                .filter(|(id, _)| !<LectureKeyCommitments<T>>::contains_key(&parent_id, id));
+
+            let course_id = Self::mint(
+                &sender,
+                parent.name.clone(),
+                parent.category.clone(),
+                parent.image_url.clone(),
+                parent.description.clone(),
+            )?;
+            <Courses<T>>::mutate(&course_id, |c| {
+                if let Some(course) = c {
+                    course.parent = Some(parent_id);
+                    // Later license changes of the parent do not alter the terms of the fork.
+                    if let License::CommercialWithRoyalty(share) = parent.license {
+                        course.parent_royalty = Some(share);
+                    }
+                    // Share-alike and royalty terms carry over to the derivative.
+                    if parent.license != License::CcBy {
+                        course.license = parent.license;
+                    }
+                }
+            });
+            for (_, mut lecture) in lectures {
+                lecture.owner = sender.clone();
+                lecture.release = None;
+                let lecture_id = Self::next_lecture_id(&course_id)?;
+                <Lectures<T>>::insert(&course_id, lecture_id, lecture);
+            }
+
+            Self::deposit_event(Event::Created(sender.clone(), course_id));
+            Self::deposit_event(Event::CourseForked(sender, parent_id, course_id));
+            Ok(())
+        }
+
+        /// Set or clear the royalty paid on secondary sales of the tokens of a class.
+        #[pallet::weight(100)]
+        pub fn set_class_royalty(
+            origin: OriginFor<T>,
+            class_id: ClassId,
+            royalty: Option<ClassRoyalty<T::AccountId>>,
+        ) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+
+            let class = Self::classes(class_id).ok_or(<Error<T>>::ClassNotFound)?;
+            ensure!(class.owner == sender, <Error<T>>::NotClassOwner);
+            ensure!(
+                royalty.as_ref().map_or(true, |r| r.rate <= T::MaxClassRoyalty::get()),
+                <Error<T>>::RoyaltyTooHigh
+            );
+            <ClassRoyalties<T>>::set(class_id, royalty.clone());
+
+            Self::deposit_event(Event::ClassRoyaltySet(class_id, royalty));
+            Ok(())
+        }
+
+        /// List a token for sale at a fixed price, or update the price of its listing.
+        ///
+        /// The sale pays the class royalty in force when the token was listed.
+        #[pallet::weight(100)]
+        pub fn list_token(
+            origin: OriginFor<T>,
+            class_id: ClassId,
+            token_id: TokenId,
+            price: BalanceOf<T>,
+        ) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+
+            Self::ensure_transferable_by(class_id, token_id, &sender)?;
+            let listing = Listing::<T> {
+                price,
+                royalty: Self::class_royalties(class_id),
             };
-            let lecture_id = T::Hashing::hash_of(&lecture);
+            <Listings<T>>::insert(class_id, token_id, listing);
 
-            <Lectures<T>>::insert(course_id, lecture_id, lecture);
+            Self::deposit_event(Event::TokenListed(sender, class_id, token_id, price));
             Ok(())
         }
 
-        /// Add a lecture to a course.
+        /// Remove a token from sale.
+        #[pallet::weight(100)]
+        pub fn delist_token(origin: OriginFor<T>, class_id: ClassId, token_id: TokenId) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+
+            let token = Self::tokens(class_id, token_id).ok_or(<Error<T>>::TokenNotFound)?;
+            ensure!(token.owner == sender, <Error<T>>::NotTokenOwner);
+            ensure!(<Listings<T>>::contains_key(class_id, token_id), <Error<T>>::TokenNotForSale);
+            <Listings<T>>::remove(class_id, token_id);
+
+            Self::deposit_event(Event::TokenDelisted(sender, class_id, token_id));
+            Ok(())
+        }
+
+        /// Buy a listed token, paying at most `bid_price`.
+        ///
+        /// The royalty recorded in the listing, if any, is paid out of the listing price.
+        #[transactional]
         #[pallet::weight(100)]
-        pub fn update_lecture(origin: OriginFor<T>, course_id: T::Hash, lecture_id: T::Hash, name: Vec<u8>, contents: Vec<u8>) -> DispatchResult {
-            let sender = ensure_signed(origin)?;
+        pub fn buy_token(
+            origin: OriginFor<T>,
+            class_id: ClassId,
+            token_id: TokenId,
+            bid_price: BalanceOf<T>,
+        ) -> DispatchResult {
+            let buyer = ensure_signed(origin)?;
 
-            ensure!(Self::is_course_owner(&course_id, &sender)?, <Error<T>>::NotCourseOwner);
-            // TODO: update lecture
+            let token = Self::tokens(class_id, token_id).ok_or(<Error<T>>::TokenNotFound)?;
+            ensure!(token.owner != buyer, <Error<T>>::BuyerIsTokenOwner);
+            let listing = Self::listings(class_id, token_id).ok_or(<Error<T>>::TokenNotForSale)?;
+            let price = listing.price;
+            ensure!(price <= bid_price, <Error<T>>::TokenBidPriceTooLow);
+            ensure!(T::Currency::free_balance(&buyer) >= price, <Error<T>>::NotEnoughBalance);
+
+            let seller = token.owner;
+            Self::settle_token_sale(class_id, token_id, &buyer, &seller, price, listing.royalty)?;
+            Self::transfer_token_to(class_id, token_id, &seller, &buyer)?;
+
+            Self::deposit_event(Event::TokenBought(buyer, seller, class_id, token_id, price));
             Ok(())
         }
 
-
-        /// Remove a lecture from the course.
+        /// Transfer a token to another account, cancelling its listing.
         #[pallet::weight(100)]
-        pub fn remove_lecture(origin: OriginFor<T>, course_id: T::Hash, lecture_id: T::Hash) -> DispatchResult {
+        pub fn transfer_token(
+            origin: OriginFor<T>,
+            class_id: ClassId,
+            token_id: TokenId,
+            to: T::AccountId,
+        ) -> DispatchResult {
             let sender = ensure_signed(origin)?;
 
-            // ACTION #1a: Checking Course owner
-            ensure!(Self::is_course_owner(&course_id, &sender)?, <Error<T>>::NotCourseOwner);
-            <Lectures<T>>::remove(course_id, lecture_id);
-            Ok(())
+            ensure!(to != sender, <Error<T>>::TransferToSelf);
+            Self::ensure_transferable_by(class_id, token_id, &sender)?;
+            Self::transfer_token_to(class_id, token_id, &sender, &to)
         }
 
-        /// Set the name for a Course.
+        /// Define a badge minted to every learner meeting `condition`.
         ///
-        /// Updates Course name and updates storage.
+        /// Rules limited to a course can be defined by its owner, global rules by `T::RewardOrigin`.
+        /// Each course, and governance, can keep up to `T::MaxBadgeRules` active rules.
+        #[transactional]
         #[pallet::weight(100)]
-        pub fn update_name(
+        pub fn create_badge_rule(
             origin: OriginFor<T>,
-            course_id: T::Hash,
-            new_name: Vec<u8>,
+            course: Option<T::Hash>,
+            name: Vec<u8>,
+            condition: BadgeCondition,
         ) -> DispatchResult {
-            let sender = ensure_signed(origin)?;
+            match &course {
+                Some(course_id) => {
+                    Self::ensure_owner_or_reward_origin(origin, course_id)?;
+                }
+                None => {
+                    T::RewardOrigin::ensure_origin(origin)?;
+                }
+            }
 
-            // ACTION #1a: Checking Course owner
-            ensure!(Self::is_course_owner(&course_id, &sender)?, <Error<T>>::NotCourseOwner);
+            let rule_id = <NextBadgeRuleId<T>>::try_mutate(|id| -> Result<BadgeRuleId, DispatchError> {
+                let current_id = *id;
+                *id = id.checked_add(1).ok_or(<Error<T>>::BadgeRuleCntOverflow)?;
+                Ok(current_id)
+            })?;
+            <ActiveBadgeRules<T>>::try_mutate(&course, |rules| rules.try_push(rule_id))
+                .map_err(|_| <Error<T>>::TooManyBadgeRules)?;
+            let class_id = Self::create_class(&T::PalletId::get().into_account(), TokenType::BoundToAddress)?;
+            let rule = BadgeRule::<T> { name, course, condition, class_id };
+            <BadgeRules<T>>::insert(rule_id, rule);
 
-            let mut course = Self::courses(&course_id).ok_or(<Error<T>>::CourseNotExist)?;
+            Self::deposit_event(Event::BadgeRuleCreated(rule_id, course, class_id));
+            Ok(())
+        }
 
-            // ACTION #2: Set the Course price and update new Course infomation to storage.
-            course.name = new_name.clone();
-            <Courses<T>>::insert(&course_id, course);
+        /// Stop awarding a badge. Badges already earned are kept.
+        #[pallet::weight(100)]
+        pub fn remove_badge_rule(origin: OriginFor<T>, rule_id: BadgeRuleId) -> DispatchResult {
+            let rule = Self::badge_rules(rule_id).ok_or(<Error<T>>::BadgeRuleNotExist)?;
+            match &rule.course {
+                Some(course_id) => {
+                    Self::ensure_owner_or_reward_origin(origin, course_id)?;
+                }
+                None => {
+                    T::RewardOrigin::ensure_origin(origin)?;
+                }
+            }
 
-            // ACTION #3: Deposit a "NameSet" event.
-            // Deposit a "NameSet" event.
-            Self::deposit_event(Event::NameSet(sender, course_id, new_name));
+            <BadgeRules<T>>::remove(rule_id);
+            <ActiveBadgeRules<T>>::mutate(&rule.course, |rules| rules.retain(|id| *id != rule_id));
 
+            Self::deposit_event(Event::BadgeRuleRemoved(rule_id));
             Ok(())
         }
     }
@@ -449,9 +3285,15 @@ pub mod pallet {
                 image_url,
                 description,
                 live: false,
+                price: None,
+                currency: PaymentCurrency::Native,
+                license: License::AllRightsReserved,
+                parent: None,
+                parent_royalty: None,
             };
 
-            let course_id = T::Hashing::hash_of(&course);
+            let course_id = T::Hashing::hash_of(&(b"course", owner, Self::next_nonce()?));
+            ensure!(!<Courses<T>>::contains_key(&course_id), <Error<T>>::CourseAlreadyExists);
 
             // Performs this operation first as it may fail
             let new_cnt = Self::course_cnt().checked_add(1)
@@ -466,6 +3308,892 @@ pub mod pallet {
             <CourseCnt<T>>::put(new_cnt);
             Ok(course_id)
         }
+        /// Weight of a bulk lecture call, proportional to the sections, lectures and bytes it stores.
+        pub fn bulk_weight(sections: &[SectionInput]) -> Weight {
+            let (lectures, bytes) = sections.iter().fold((0u64, 0u64), |(lectures, bytes), section| {
+                let section_bytes = section.lectures.iter()
+                    .map(|l| (l.name.len() + l.contents.len()) as u64)
+                    .sum::<u64>();
+                (
+                    lectures.saturating_add(section.lectures.len() as u64),
+                    bytes.saturating_add(section.name.len() as u64).saturating_add(section_bytes),
+                )
+            });
+            let sections = sections.len() as u64;
+            let db = T::DbWeight::get();
+            100u64
+                // The course itself, for `create_course_with_lectures`.
+                .saturating_add(db.reads_writes(4, 4))
+                .saturating_add(db.reads_writes(sections, sections.saturating_mul(2)))
+                .saturating_add(db.reads_writes(lectures.saturating_mul(2), lectures.saturating_mul(3)))
+                .saturating_add(bytes)
+        }
+
+        /// Weight of a lecture completion, bounded by the edition, learning path and badge rule limits.
+        pub fn completion_weight() -> Weight {
+            let db = T::DbWeight::get();
+            let lectures = T::MaxEditionLectures::get() as u64;
+            let paths = T::MaxLearnerPaths::get() as u64;
+            let path_courses = T::MaxPathCourses::get() as u64;
+            // Global rules and the rules of the course.
+            let rules = (T::MaxBadgeRules::get() as u64).saturating_mul(2);
+            100u64
+                .saturating_add(db.reads_writes(24, 18))
+                .saturating_add(db.reads(lectures.saturating_mul(2)))
+                .saturating_add(db.reads_writes(
+                    paths.saturating_mul(path_courses.saturating_add(2)),
+                    paths.saturating_mul(5),
+                ))
+                .saturating_add(db.reads_writes(rules.saturating_mul(4), rules.saturating_mul(5)))
+        }
+
+        /// Weight of drawing reviewers from a pool of up to `MaxReviewerPool` candidates.
+        pub fn reviewer_assignment_weight() -> Weight {
+            let pool = T::MaxReviewerPool::get() as u64;
+            100u64
+                .saturating_add(T::DbWeight::get().reads_writes(10, 3))
+                .saturating_add(pool.saturating_mul(100))
+        }
+
+        /// Weight of `review_assignment`: the reputation of every reviewer, then the completion.
+        pub fn review_weight() -> Weight {
+            let reviewers = T::MaxReviewers::get() as u64;
+            Self::completion_weight()
+                .saturating_add(T::DbWeight::get().reads_writes(reviewers.saturating_add(6), reviewers.saturating_add(6)))
+        }
+
+        /// Weight of `take_down_course`, which refunds up to `MaxComplaints` deposits.
+        pub fn takedown_weight() -> Weight {
+            let complaints = T::MaxComplaints::get() as u64;
+            100u64.saturating_add(T::DbWeight::get().reads_writes(
+                complaints.saturating_add(4),
+                complaints.saturating_mul(2).saturating_add(5),
+            ))
+        }
+
+        /// Weight of `remove_lecture`, which clears up to `MaxTranslations` translations.
+        pub fn lecture_removal_weight() -> Weight {
+            let translations = T::MaxTranslations::get() as u64;
+            100u64.saturating_add(T::DbWeight::get().reads_writes(3, translations.saturating_add(6)))
+        }
+
+        /// Weight of `fork_course`, which copies up to `MaxEditionLectures` lectures.
+        pub fn fork_weight() -> Weight {
+            let lectures = T::MaxEditionLectures::get() as u64;
+            100u64.saturating_add(T::DbWeight::get().reads_writes(
+                lectures.saturating_mul(3).saturating_add(8),
+                lectures.saturating_mul(2).saturating_add(6),
+            ))
+        }
+
+        /// Weight of snapshotting the lecture set of a course as a new edition.
+        pub fn edition_weight() -> Weight {
+            let lectures = T::MaxEditionLectures::get() as u64;
+            100u64.saturating_add(T::DbWeight::get().reads_writes(lectures.saturating_add(6), 6))
+        }
+
+        /// Weight of `migrate_progress`: every completion of both editions, then the course completion.
+        pub fn migration_weight() -> Weight {
+            let lectures = (T::MaxEditionLectures::get() as u64).saturating_mul(2);
+            Self::completion_weight()
+                .saturating_add(T::DbWeight::get().reads_writes(lectures.saturating_add(4), lectures.saturating_add(1)))
+        }
+
+        /// Weight of an enrollment: prerequisites, coupon, payment split and bookkeeping.
+        pub fn enrollment_weight() -> Weight {
+            let prerequisites = T::MaxPrerequisites::get() as u64;
+            100u64.saturating_add(T::DbWeight::get().reads_writes(prerequisites.saturating_add(12), 10))
+        }
+
+        /// Weight of `set_enrollment_rules`, proportional to the prerequisites checked.
+        pub fn enrollment_rules_weight(prerequisites: usize) -> Weight {
+            100u64.saturating_add(T::DbWeight::get().reads_writes((prerequisites as u64).saturating_add(1), 1))
+        }
+
+        /// Weight of `revoke_coupon`, which clears up to `MaxCouponRedemptions` redemptions.
+        pub fn coupon_revocation_weight() -> Weight {
+            let redemptions = T::MaxCouponRedemptions::get() as u64;
+            100u64.saturating_add(T::DbWeight::get().reads_writes(1, redemptions.saturating_add(1)))
+        }
+
+        /// Weight of `create_learning_path`, proportional to the courses of the path.
+        pub fn path_creation_weight(courses: usize) -> Weight {
+            100u64.saturating_add(T::DbWeight::get().reads_writes((courses as u64).saturating_add(2), 4))
+        }
+
+        /// Weight of `enroll_in_path`: one enrollment per course, then the certificate check.
+        pub fn path_enrollment_weight() -> Weight {
+            let courses = T::MaxPathCourses::get() as u64;
+            Self::enrollment_weight()
+                .saturating_mul(courses)
+                .saturating_add(T::DbWeight::get().reads_writes(courses.saturating_add(4), 8))
+        }
+
+        // Adds bulk sections of lectures to a course.
+        fn add_sections(owner: &T::AccountId, course_id: &T::Hash, sections: Vec<SectionInput>) -> DispatchResult {
+            ensure!(sections.len() <= T::MaxBulkLectures::get() as usize, <Error<T>>::TooManySections);
+            let total: usize = sections.iter().map(|s| s.lectures.len()).sum();
+            ensure!(total <= T::MaxBulkLectures::get() as usize, <Error<T>>::TooManyLectures);
+
+            for input in sections {
+                let section_id = <NextSectionId<T>>::try_mutate(course_id, |id| -> Result<SectionId, DispatchError> {
+                    let current_id = *id;
+                    *id = id.checked_add(1).ok_or(<Error<T>>::SectionCntOverflow)?;
+                    Ok(current_id)
+                })?;
+
+                let mut lectures = Vec::with_capacity(input.lectures.len());
+                for lecture in input.lectures {
+                    if let LectureKind::Assignment { reviewers } = lecture.kind {
+                        ensure!(
+                            reviewers > 0 && reviewers as u32 <= T::MaxReviewers::get(),
+                            <Error<T>>::InvalidReviewerCount
+                        );
+                    }
+                    let lecture_id = Self::next_lecture_id(course_id)?;
+                    <Lectures<T>>::insert(course_id, lecture_id, Lecture::<T> {
+                        name: lecture.name,
+                        contents: lecture.contents,
+                        owner: owner.clone(),
+                        kind: lecture.kind,
+                        release: None,
+                    });
+                    <LectureSections<T>>::insert(course_id, lecture_id, section_id);
+                    lectures.push(lecture_id);
+                }
+
+                let count = lectures.len() as u32;
+                let section = Section::<T> {
+                    name: input.name,
+                    lectures: lectures.try_into().map_err(|_| <Error<T>>::TooManyLectures)?,
+                };
+                <Sections<T>>::insert(course_id, section_id, section);
+                Self::deposit_event(Event::SectionCreated(*course_id, section_id, count));
+            }
+            Ok(())
+        }
+
+        // Takes the next value of the id Nonce.
+        fn next_nonce() -> Result<u64, Error<T>> {
+            <Nonce<T>>::try_mutate(|nonce| {
+                let current = *nonce;
+                *nonce = nonce.checked_add(1).ok_or(<Error<T>>::NonceOverflow)?;
+                Ok(current)
+            })
+        }
+
+        // Derives a fresh Lecture id, independent of the lecture contents.
+        fn next_lecture_id(course_id: &T::Hash) -> Result<T::Hash, Error<T>> {
+            let lecture_id = T::Hashing::hash_of(&(b"lecture", course_id, Self::next_nonce()?));
+            ensure!(!<Lectures<T>>::contains_key(course_id, &lecture_id), <Error<T>>::LectureAlreadyExists);
+            Ok(lecture_id)
+        }
+
+        /// Enrollment, completion and revenue counters of a course, used by the runtime API.
+        pub fn course_analytics(course_id: &T::Hash) -> CourseAnalytics<T::Hash, T::AssetId, BalanceOf<T>> {
+            let edition = Self::current_edition(course_id);
+            CourseAnalytics {
+                enrollments: Self::enrollment_count(course_id),
+                lecture_completions: Self::edition_lectures(course_id, edition)
+                    .into_iter()
+                    .map(|(lecture_id, _)| (lecture_id, Self::lecture_completion_count(course_id, lecture_id)))
+                    .collect(),
+                completions: Self::completion_count(course_id),
+                revenue: <CourseRevenue<T>>::iter_prefix(course_id).collect(),
+            }
+        }
+
+        /// Whether `who` is a verified instructor, used by the runtime API.
+        pub fn is_verified_instructor(who: &T::AccountId) -> bool {
+            T::InstructorVerifier::is_verified(who)
+        }
+
+        /// The completion record of `learner` for `course_id`, used by the runtime API.
+        pub fn course_completion(learner: &T::AccountId, course_id: &T::Hash) -> Option<CourseCompletion<T>> {
+            Self::courses_completed(learner, course_id)
+        }
+
+        /// The account holding the reward budget of a course.
+        pub fn reward_pot(course_id: &T::Hash) -> T::AccountId {
+            T::PalletId::get().into_sub_account(course_id)
+        }
+
+        // Helper to record a verified lecture completion and pay the matching rewards.
+        pub fn do_complete_lecture(learner: &T::AccountId, course_id: &T::Hash, lecture_id: &T::Hash) -> DispatchResult {
+            let course = Self::courses(course_id).ok_or(<Error<T>>::CourseNotExist)?;
+            ensure!(course.live, <Error<T>>::CourseNotPublished);
+            ensure!(Self::has_access(&course, course_id, learner), <Error<T>>::NotEnrolled);
+            let lecture = Self::lectures(course_id, lecture_id).ok_or(<Error<T>>::LectureNotExist)?;
+            ensure!(Self::is_released(&lecture, course_id, Some(learner)), <Error<T>>::LectureNotReleased);
+            ensure!(
+                !<LecturesCompleted<T>>::contains_key((learner.clone(), *course_id, *lecture_id)),
+                <Error<T>>::LectureAlreadyCompleted
+            );
+            // Encrypted lectures can only be studied once the content key was granted.
+            ensure!(
+                course.owner == *learner
+                    || !<LectureKeyCommitments<T>>::contains_key(course_id, lecture_id)
+                    || <WrappedKeys<T>>::contains_key((*course_id, *lecture_id, learner.clone())),
+                <Error<T>>::LectureKeyNotGranted
+            );
+
+            // Learners study the edition current when they first complete a lecture.
+            let edition = match Self::learner_editions(learner, course_id) {
+                Some(edition) => edition,
+                None => {
+                    let edition = Self::current_edition(course_id);
+                    <LearnerEditions<T>>::insert(learner, course_id, edition);
+                    edition
+                }
+            };
+            let edition_lectures = Self::edition_lectures(course_id, edition);
+            ensure!(
+                edition_lectures.iter().any(|(id, _)| id == lecture_id),
+                <Error<T>>::LectureNotInEdition
+            );
+
+            let lecture_completed = LectureCompleted::<T> {
+                owner: learner.clone(),
+                edition,
+            };
+            <LecturesCompleted<T>>::insert((learner.clone(), *course_id, *lecture_id), lecture_completed);
+            <LectureCompletionCount<T>>::mutate(course_id, lecture_id, |count| *count = count.saturating_add(1));
+            Self::deposit_event(Event::LectureCompleted(learner.clone(), *course_id, *lecture_id));
+            Self::record_cohort_completion(learner, course_id, lecture_id);
+            Self::record_activity(learner, course_id);
+
+            // Owners cannot farm the rewards of their own course, and self-attested completions
+            // are only rewarded for learners who paid for their enrollment.
+            let paid = Self::enrollments(learner, course_id).map_or(false, |e| !e.paid.is_zero());
+            let peer_graded = matches!(lecture.kind, LectureKind::Assignment { .. });
+            let rewarded = course.owner != *learner;
+            // A lecture is only rewarded once, even when it is completed again in a new edition.
+            let key = (learner.clone(), *course_id, *lecture_id);
+            if let (Some(plan), true, true, false) =
+                (Self::reward_plans(course_id), rewarded, paid || peer_graded, <RewardedLectures<T>>::contains_key(&key))
+            {
+                Self::pay_reward(learner, course_id, plan.per_lecture)?;
+                <RewardedLectures<T>>::insert(&key, ());
+            }
+
+            let completed = Self::try_complete_course(learner, course_id, &course, edition, &edition_lectures)?;
+            Self::evaluate_badges(learner, course_id, &course, completed)
+        }
+
+        // Records the completion of `edition` once `learner` completed all of its lectures.
+        // Returns whether the course was completed.
+        fn try_complete_course(
+            learner: &T::AccountId,
+            course_id: &T::Hash,
+            course: &Course<T>,
+            edition: EditionId,
+            edition_lectures: &[(T::Hash, T::Hash)],
+        ) -> Result<bool, DispatchError> {
+            // Lectures removed since the edition was snapshotted can no longer be completed.
+            let all_completed = edition_lectures.iter()
+                .filter(|(id, _)| <Lectures<T>>::contains_key(course_id, id))
+                .all(|(id, _)| <LecturesCompleted<T>>::contains_key((learner.clone(), *course_id, *id)));
+            let previous = Self::courses_completed(learner, course_id);
+            if !all_completed || previous.as_ref().map_or(false, |c| c.edition >= edition) {
+                return Ok(false);
+            }
+
+            let completion = CourseCompletion::<T> {
+                owner: learner.clone(),
+                completed_at: <frame_system::Pallet<T>>::block_number(),
+                edition,
+            };
+            <CoursesCompleted<T>>::insert(learner, course_id, completion);
+            if previous.is_none() {
+                <CompletionCount<T>>::mutate(course_id, |count| *count = count.saturating_add(1));
+                <CategoryCompletions<T>>::mutate(learner, T::Hashing::hash_of(&course.category), |count| {
+                    *count = count.saturating_add(1)
+                });
+                if let Some(start) = Self::course_starts(learner, course_id) {
+                    let duration = T::Time::now().saturating_sub(start).saturated_into::<u64>();
+                    Self::update_leaderboard(course_id, LeaderboardKind::Speed, learner, duration);
+                }
+            }
+            Self::deposit_event(Event::CourseCompleted(learner.clone(), *course_id));
+            // The completion bonus is only paid for the first edition completed.
+            let paid = Self::enrollments(learner, course_id).map_or(false, |e| !e.paid.is_zero());
+            if let (Some(plan), true, true, true) =
+                (Self::reward_plans(course_id), course.owner != *learner, paid, previous.is_none())
+            {
+                Self::pay_reward(learner, course_id, plan.completion_bonus)?;
+            }
+            for (path_id, _) in <PathEnrollments<T>>::iter_prefix(learner) {
+                let path = Self::learning_paths(path_id).ok_or(<Error<T>>::LearningPathNotExist)?;
+                if path.courses.contains(course_id) {
+                    Self::try_issue_path_certificate(learner, path_id, &path)?;
+                }
+            }
+            Ok(true)
+        }
+
+        // The current day, in `T::DayLength` buckets of `T::Time`.
+        fn today() -> u64 {
+            let day_length = T::DayLength::get();
+            if day_length.is_zero() {
+                return 0;
+            }
+            (T::Time::now() / day_length).saturated_into::<u64>()
+        }
+
+        // Records a completed lecture in the activity history of `learner`.
+        fn record_activity(learner: &T::AccountId, course_id: &T::Hash) {
+            <LearnerLectureCount<T>>::mutate(learner, |count| *count = count.saturating_add(1));
+            <CourseLectureCount<T>>::mutate(learner, course_id, |count| *count = count.saturating_add(1));
+            if !<CourseStarts<T>>::contains_key(learner, course_id) {
+                <CourseStarts<T>>::insert(learner, course_id, T::Time::now());
+            }
+
+            let today = Self::today();
+            let current = <Streaks<T>>::mutate(learner, |streak| {
+                if streak.current == 0 || today > streak.last_day.saturating_add(1) {
+                    streak.current = 1;
+                } else if today == streak.last_day.saturating_add(1) {
+                    streak.current = streak.current.saturating_add(1);
+                }
+                streak.last_day = today;
+                streak.current
+            });
+            <LongestStreaks<T>>::mutate(learner, |longest| *longest = (*longest).max(current));
+        }
+
+        /// The current and longest daily activity streaks of `learner`, used by the runtime API.
+        ///
+        /// The current streak drops to zero once a full day passes without activity.
+        pub fn learner_streak(learner: &T::AccountId) -> (u32, u32) {
+            let streak = Self::streaks(learner);
+            let current = if Self::today() > streak.last_day.saturating_add(1) { 0 } else { streak.current };
+            (current, Self::longest_streaks(learner))
+        }
+
+        /// The top learners of a course for the given criteria, best first, used by the runtime API.
+        pub fn leaderboard(course_id: &T::Hash, kind: LeaderboardKind) -> Vec<(T::AccountId, u64)> {
+            Self::leaderboards(course_id, kind).into_inner()
+        }
+
+        // Places `learner` on a course leaderboard, keeping its best `MaxLeaderboardSize` entries.
+        fn update_leaderboard(course_id: &T::Hash, kind: LeaderboardKind, learner: &T::AccountId, value: u64) {
+            let better = |a: u64, b: u64| match kind {
+                LeaderboardKind::Speed => a < b,
+                LeaderboardKind::Score => a > b,
+            };
+            <Leaderboards<T>>::mutate(course_id, kind, |board| {
+                let mut entries = board.clone().into_inner();
+                entries.retain(|(account, _)| account != learner);
+                let position = entries.iter().position(|(_, v)| better(value, *v)).unwrap_or(entries.len());
+                entries.insert(position, (learner.clone(), value));
+                entries.truncate(T::MaxLeaderboardSize::get() as usize);
+                if let Ok(entries) = entries.try_into() {
+                    *board = entries;
+                }
+            });
+        }
+
+        // Mints the badges `learner` just earned, once per rule.
+        fn evaluate_badges(
+            learner: &T::AccountId,
+            course_id: &T::Hash,
+            course: &Course<T>,
+            course_completed: bool,
+        ) -> DispatchResult {
+            let rule_ids = Self::active_badge_rules(None::<T::Hash>)
+                .into_iter()
+                .chain(Self::active_badge_rules(Some(*course_id)));
+            for rule_id in rule_ids {
+                if <BadgesAwarded<T>>::contains_key(learner, rule_id) {
+                    continue;
+                }
+                let rule = match Self::badge_rules(rule_id) {
+                    Some(rule) => rule,
+                    None => continue,
+                };
+
+                let earned = match &rule.condition {
+                    BadgeCondition::LecturesCompleted(count) => {
+                        let completed = match rule.course {
+                            Some(_) => Self::course_lecture_count(learner, course_id),
+                            None => Self::learner_lecture_count(learner),
+                        };
+                        completed >= *count
+                    }
+                    BadgeCondition::CourseWithin(duration) => {
+                        course_completed && Self::course_starts(learner, course_id).map_or(false, |start| {
+                            T::Time::now().saturating_sub(start).saturated_into::<u64>() <= *duration
+                        })
+                    }
+                    BadgeCondition::CategoryCourses { category, count } => {
+                        course_completed
+                            && course.category == *category
+                            && Self::category_completions(learner, T::Hashing::hash_of(category)) >= *count
+                    }
+                    BadgeCondition::Streak(days) => Self::streaks(learner).current >= *days,
+                };
+                if earned {
+                    let token_id = Self::mint_token(rule.class_id, learner)?;
+                    <BadgesAwarded<T>>::insert(learner, rule_id, token_id);
+                    Self::deposit_event(Event::BadgeAwarded(learner.clone(), rule_id, rule.class_id, token_id));
+                }
+            }
+            Ok(())
+        }
+
+        // Snapshots the current lecture set of a course as its next edition.
+        fn snapshot_edition(course_id: &T::Hash) -> Result<EditionId, DispatchError> {
+            // Reading one lecture past the bound is enough to reject oversized courses.
+            let lectures: Vec<(T::Hash, T::Hash)> = <Lectures<T>>::iter_prefix(course_id)
+                .take(T::MaxEditionLectures::get().saturating_add(1) as usize)
+                .map(|(id, lecture)| (id, Self::lecture_content_hash(&lecture)))
+                .collect();
+            let edition = Self::current_edition(course_id)
+                .checked_add(1)
+                .ok_or(<Error<T>>::EditionCntOverflow)?;
+            let snapshot = Edition::<T> {
+                lectures: lectures.try_into().map_err(|_| <Error<T>>::TooManyLectures)?,
+                published_at: <frame_system::Pallet<T>>::block_number(),
+            };
+            <Editions<T>>::insert(course_id, edition, snapshot);
+            <CurrentEdition<T>>::insert(course_id, edition);
+
+            Self::deposit_event(Event::EditionPublished(*course_id, edition));
+            Ok(edition)
+        }
+
+        /// The `(lecture_id, content_hash)` pairs of an edition.
+        ///
+        /// Courses published before editions existed have no snapshot, up to `MaxEditionLectures`
+        /// of their live lectures are used instead.
+        pub fn edition_lectures(course_id: &T::Hash, edition: EditionId) -> Vec<(T::Hash, T::Hash)> {
+            match Self::editions(course_id, edition) {
+                Some(snapshot) => snapshot.lectures.into_inner(),
+                None => <Lectures<T>>::iter_prefix(course_id)
+                    .take(T::MaxEditionLectures::get() as usize)
+                    .map(|(id, lecture)| (id, Self::lecture_content_hash(&lecture)))
+                    .collect(),
+            }
+        }
+
+        // Hash of the parts of a lecture that define what learners study.
+        fn lecture_content_hash(lecture: &Lecture<T>) -> T::Hash {
+            T::Hashing::hash_of(&(&lecture.name, &lecture.contents, &lecture.kind))
+        }
+
+        // Price `learner` pays to enroll in a course, redeeming the coupon if any.
+        fn enrollment_price(
+            learner: &T::AccountId,
+            course_id: &T::Hash,
+            coupon: Option<T::Hash>,
+        ) -> Result<BalanceOf<T>, DispatchError> {
+            let course = Self::courses(course_id).ok_or(<Error<T>>::CourseNotExist)?;
+            let price = course.price.unwrap_or_else(Zero::zero);
+            let code = match coupon {
+                Some(code) => code,
+                None => return Ok(price),
+            };
+
+            let price = <Coupons<T>>::try_mutate(&code, |c| -> Result<BalanceOf<T>, DispatchError> {
+                let coupon = c.as_mut().ok_or(<Error<T>>::CouponNotExist)?;
+                ensure!(
+                    <frame_system::Pallet<T>>::block_number() <= coupon.expires_at,
+                    <Error<T>>::CouponExpired
+                );
+                ensure!(coupon.redemptions < coupon.max_redemptions, <Error<T>>::CouponExhausted);
+                let applicable = match &coupon.course {
+                    Some(scope) => scope == course_id,
+                    None => coupon.owner == course.owner,
+                };
+                ensure!(applicable, <Error<T>>::CouponNotApplicable);
+                ensure!(
+                    !<CouponRedemptions<T>>::contains_key(&code, learner),
+                    <Error<T>>::CouponAlreadyRedeemed
+                );
+
+                coupon.redemptions += 1;
+                Ok(match coupon.discount {
+                    Discount::Percentage(percent) => price.saturating_sub(percent * price),
+                    Discount::Fixed(amount) => price.saturating_sub(amount),
+                })
+            })?;
+            <CouponRedemptions<T>>::insert(&code, learner, <frame_system::Pallet<T>>::block_number());
+
+            Self::deposit_event(Event::CouponRedeemed(learner.clone(), code, *course_id, price));
+            Ok(price)
+        }
+
+        /// Whether a lecture is released for `learner`, or for everyone if `None`.
+        ///
+        /// Lectures released relative to enrollment stay hidden from accounts not enrolled.
+        pub fn is_released(lecture: &Lecture<T>, course_id: &T::Hash, learner: Option<&T::AccountId>) -> bool {
+            let now = <frame_system::Pallet<T>>::block_number();
+            match (lecture.release, learner) {
+                (None, _) => true,
+                (Some(_), Some(learner)) if lecture.owner == *learner => true,
+                (Some(ReleaseSchedule::At(at)), _) => now >= at,
+                (Some(ReleaseSchedule::AfterEnrollment(delay)), Some(learner)) => {
+                    Self::enrollments(learner, course_id)
+                        .map_or(false, |e| now >= e.enrolled_at.saturating_add(delay))
+                }
+                (Some(ReleaseSchedule::AfterEnrollment(_)), None) => false,
+            }
+        }
+
+        // Counts a new translation of a course, or of one of its lectures, against `T::MaxTranslations`.
+        fn count_translation(course_id: &T::Hash, lecture_id: Option<T::Hash>) -> DispatchResult {
+            <TranslationCount<T>>::try_mutate(course_id, lecture_id, |count| -> DispatchResult {
+                ensure!(*count < T::MaxTranslations::get(), <Error<T>>::TooManyTranslations);
+                *count += 1;
+                Ok(())
+            })
+        }
+
+        // Checks `who` is the owner or a translator of the course.
+        fn ensure_translator(course_id: &T::Hash, who: &T::AccountId) -> DispatchResult {
+            ensure!(
+                Self::is_course_owner(course_id, who)? || <Translators<T>>::contains_key(course_id, who),
+                <Error<T>>::NotTranslator
+            );
+            Ok(())
+        }
+
+        // Language tags are case-insensitive, they are stored in lowercase so `pt-BR` matches `pt-br`.
+        fn language_tag(language: &[u8]) -> Result<LanguageTag<T>, Error<T>> {
+            language.to_ascii_lowercase().try_into().map_err(|_| <Error<T>>::LanguageTagTooLong)
+        }
+
+        // Tags to look translations up with: the preferred language, then its primary subtag,
+        // e.g. `pt-br` then `pt`.
+        fn language_fallbacks(language: &[u8]) -> Vec<LanguageTag<T>> {
+            let mut tags = Vec::new();
+            if let Ok(tag) = Self::language_tag(language) {
+                tags.push(tag);
+            }
+            if let Some(position) = language.iter().position(|c| *c == b'-') {
+                if let Ok(tag) = Self::language_tag(&language[..position]) {
+                    tags.push(tag);
+                }
+            }
+            tags
+        }
+
+        /// The name and description of a course in `language`, falling back to the default
+        /// metadata. Used by the runtime API.
+        pub fn localized_course(course_id: &T::Hash, language: Vec<u8>) -> Option<LocalizedText> {
+            let course = Self::courses(course_id)?;
+            let translated = Self::language_fallbacks(&language).into_iter()
+                .find_map(|tag| Self::course_translations(course_id, &tag).map(|t| (tag, t)));
+            Some(match translated {
+                Some((tag, translation)) => LocalizedText {
+                    language: Some(tag.into_inner()),
+                    name: translation.name,
+                    body: translation.body,
+                },
+                None => LocalizedText { language: None, name: course.name, body: course.description },
+            })
+        }
+
+        /// The lectures of a course released for `learner`, in `language` when translated.
+        /// Used by the runtime API.
+        pub fn localized_lectures(
+            course_id: &T::Hash,
+            learner: Option<T::AccountId>,
+            language: Vec<u8>,
+        ) -> Vec<(T::Hash, LocalizedText)> {
+            let tags = Self::language_fallbacks(&language);
+            Self::released_lectures(course_id, learner)
+                .into_iter()
+                .map(|(lecture_id, lecture)| {
+                    let translated = tags.iter().find_map(|tag| {
+                        Self::lecture_translations((*course_id, lecture_id, tag.clone())).map(|t| (tag, t))
+                    });
+                    let text = match translated {
+                        Some((tag, translation)) => LocalizedText {
+                            language: Some(tag.clone().into_inner()),
+                            name: translation.name,
+                            body: translation.body,
+                        },
+                        None => LocalizedText { language: None, name: lecture.name, body: lecture.contents },
+                    };
+                    (lecture_id, text)
+                })
+                .collect()
+        }
+
+        /// The lectures of a course released for `learner`, used by the runtime API.
+        pub fn released_lectures(course_id: &T::Hash, learner: Option<T::AccountId>) -> Vec<(T::Hash, Lecture<T>)> {
+            <Lectures<T>>::iter_prefix(course_id)
+                .filter(|(_, lecture)| Self::is_released(lecture, course_id, learner.as_ref()))
+                .collect()
+        }
+
+        // Whether `learner` can follow the course: free courses are open to everyone.
+        pub fn has_access(course: &Course<T>, course_id: &T::Hash, learner: &T::AccountId) -> bool {
+            course.price.is_none() || course.owner == *learner || <Enrollments<T>>::contains_key(learner, course_id)
+        }
+
+        // Helper to enroll `learner` in a course, charging `amount` to `payer`.
+        pub fn do_enroll(
+            payer: &T::AccountId,
+            learner: &T::AccountId,
+            course_id: &T::Hash,
+            amount: BalanceOf<T>,
+        ) -> DispatchResult {
+            let course = Self::courses(course_id).ok_or(<Error<T>>::CourseNotExist)?;
+            Self::ensure_can_enroll(learner, course_id, &course)?;
+            Self::pay_for_course(payer, &course, course_id, amount, ExistenceRequirement::KeepAlive, None)?;
+            Self::record_enrollment(learner, course_id, amount);
+            Ok(())
+        }
+
+        // Checks publication, ownership, prerequisites and capacity before enrolling `learner`.
+        fn ensure_can_enroll(learner: &T::AccountId, course_id: &T::Hash, course: &Course<T>) -> DispatchResult {
+            ensure!(course.live, <Error<T>>::CourseNotPublished);
+            ensure!(course.owner != *learner, <Error<T>>::BuyerIsCourseOwner);
+            ensure!(!<Enrollments<T>>::contains_key(learner, course_id), <Error<T>>::AlreadyEnrolled);
+            if let Some(rules) = Self::enrollment_rules(course_id) {
+                ensure!(
+                    rules.prerequisites.iter().all(|p| <CoursesCompleted<T>>::contains_key(learner, p)),
+                    <Error<T>>::PrerequisitesNotMet
+                );
+                if let Some(capacity) = rules.capacity {
+                    ensure!(Self::enrollment_count(course_id) < capacity, <Error<T>>::CourseFull);
+                }
+            }
+            Ok(())
+        }
+
+        // Transfers the price of a course from `payer` to the course owner, minus the referral
+        // commission paid to `referrer` if any.
+        fn pay_for_course(
+            payer: &T::AccountId,
+            course: &Course<T>,
+            course_id: &T::Hash,
+            amount: BalanceOf<T>,
+            existence: ExistenceRequirement,
+            referrer: Option<&T::AccountId>,
+        ) -> DispatchResult {
+            if amount.is_zero() {
+                return Ok(());
+            }
+            ensure!(Self::balance_of(&course.currency, payer) >= amount, <Error<T>>::NotEnoughBalance);
+            let mut owner_share = amount;
+            if let Some(referrer) = referrer {
+                let commission = Self::referral_commissions(course_id) * amount;
+                if !commission.is_zero() {
+                    Self::transfer_funds(&course.currency, payer, referrer, commission, existence)?;
+                    <ReferralStatistics<T>>::mutate(referrer, &course.currency, |stats| {
+                        stats.referrals = stats.referrals.saturating_add(1);
+                        stats.earned = stats.earned.saturating_add(commission);
+                    });
+                    owner_share = amount.saturating_sub(commission);
+                    Self::deposit_event(Event::ReferralPaid(referrer.clone(), payer.clone(), *course_id, commission));
+                }
+            }
+            // Derivatives of royalty-bearing courses pay the parent owner on every sale.
+            if let (Some(parent_id), Some(share)) = (course.parent, course.parent_royalty) {
+                if let Some(parent) = Self::courses(&parent_id) {
+                    let royalty = (share * amount).min(owner_share);
+                    if !royalty.is_zero() && parent.owner != course.owner {
+                        Self::transfer_funds(&course.currency, payer, &parent.owner, royalty, existence)?;
+                        owner_share = owner_share.saturating_sub(royalty);
+                        Self::deposit_event(Event::DerivativeRoyaltyPaid(parent.owner, *course_id, royalty));
+                    }
+                }
+            }
+            if !owner_share.is_zero() {
+                Self::transfer_funds(&course.currency, payer, &course.owner, owner_share, existence)?;
+            }
+            <CourseRevenue<T>>::mutate(course_id, &course.currency, |revenue| {
+                *revenue = revenue.saturating_add(amount)
+            });
+            Self::deposit_event(Event::Bought(payer.clone(), course.owner.clone(), *course_id, amount));
+            Ok(())
+        }
+
+        // Free balance of `who` in the given currency.
+        fn balance_of(currency: &PaymentCurrency<T::AssetId>, who: &T::AccountId) -> BalanceOf<T> {
+            match currency {
+                PaymentCurrency::Native => T::Currency::free_balance(who),
+                PaymentCurrency::Asset(asset) => {
+                    <T::Assets as fungibles::Inspect<T::AccountId>>::balance(*asset, who)
+                }
+            }
+        }
+
+        // Transfers `amount` in the given currency, through `T::Currency` or `T::Assets`.
+        fn transfer_funds(
+            currency: &PaymentCurrency<T::AssetId>,
+            from: &T::AccountId,
+            to: &T::AccountId,
+            amount: BalanceOf<T>,
+            existence: ExistenceRequirement,
+        ) -> DispatchResult {
+            match currency {
+                PaymentCurrency::Native => T::Currency::transfer(from, to, amount, existence),
+                PaymentCurrency::Asset(asset) => {
+                    let keep_alive = matches!(existence, ExistenceRequirement::KeepAlive);
+                    <T::Assets as fungibles::Transfer<T::AccountId>>::transfer(*asset, from, to, amount, keep_alive)
+                        .map(|_| ())
+                }
+            }
+        }
+
+        fn record_enrollment(learner: &T::AccountId, course_id: &T::Hash, amount: BalanceOf<T>) {
+            let enrollment = Enrollment::<T> {
+                enrolled_at: <frame_system::Pallet<T>>::block_number(),
+                paid: amount,
+            };
+            <Enrollments<T>>::insert(learner, course_id, enrollment);
+            <EnrollmentCount<T>>::mutate(course_id, |count| *count = count.saturating_add(1));
+
+            Self::deposit_event(Event::Enrolled(learner.clone(), *course_id, amount));
+        }
+
+        /// The account holding the payments of gifts awaiting their recipient.
+        pub fn gift_escrow() -> T::AccountId {
+            T::PalletId::get().into_sub_account(b"gifts")
+        }
+
+        // Issues the path certificate once every course of the path is completed.
+        fn try_issue_path_certificate(
+            learner: &T::AccountId,
+            path_id: PathId,
+            path: &LearningPath<T>,
+        ) -> DispatchResult {
+            if <PathCertificates<T>>::contains_key(learner, path_id) {
+                return Ok(());
+            }
+            if !path.courses.iter().all(|course_id| <CoursesCompleted<T>>::contains_key(learner, course_id)) {
+                return Ok(());
+            }
+            let token_id = Self::mint_token(path.certificate_class, learner)?;
+            <PathCertificates<T>>::insert(learner, path_id, token_id);
+            Self::deposit_event(Event::PathCompleted(learner.clone(), path_id, path.certificate_class, token_id));
+            Ok(())
+        }
+
+        // Picks `count` distinct reviewers other than the learner and `excluded` from the course
+        // reviewer pool, using a generator seeded from `T::CourseRandomness`.
+        fn assign_reviewers(
+            learner: &T::AccountId,
+            course_id: &T::Hash,
+            count: usize,
+            excluded: &[T::AccountId],
+        ) -> Result<BoundedVec<T::AccountId, T::MaxReviewers>, DispatchError> {
+            let mut candidates: Vec<T::AccountId> = Self::reviewer_pool(course_id)
+                .into_inner()
+                .into_iter()
+                .filter(|r| r != learner && !excluded.contains(r))
+                .collect();
+            ensure!(candidates.len() >= count, <Error<T>>::NotEnoughReviewers);
+
+            let nonce = Self::update_nonce();
+            let (random_seed, _) = T::CourseRandomness::random(&nonce);
+            let seed = T::Hashing::hash_of(&(random_seed, learner, course_id, nonce));
+            let mut rng = <RandomNumberGenerator<T::Hashing>>::new(seed);
+
+            let mut reviewers = Vec::with_capacity(count);
+            for _ in 0..count {
+                let ind = rng.pick_usize(candidates.len() - 1);
+                reviewers.push(candidates.swap_remove(ind));
+            }
+            reviewers.try_into().map_err(|_| <Error<T>>::InvalidReviewerCount.into())
+        }
+
+        // Ensures reviewers had `T::ReviewPeriod` blocks to score the submission.
+        fn ensure_review_period_elapsed(submission: &Submission<T>) -> DispatchResult {
+            let deadline = submission.submitted_at.saturating_add(T::ReviewPeriod::get());
+            ensure!(<frame_system::Pallet<T>>::block_number() >= deadline, <Error<T>>::ReviewPeriodNotElapsed);
+            Ok(())
+        }
+
+        // Median of the reviewer scores, averaging the two middle scores for an even count.
+        fn median_score(scores: &[(T::AccountId, u8)]) -> u8 {
+            let mut sorted: Vec<u8> = scores.iter().map(|(_, score)| *score).collect();
+            sorted.sort_unstable();
+            let mid = sorted.len() / 2;
+            if sorted.is_empty() {
+                0
+            } else if sorted.len() % 2 == 0 {
+                ((sorted[mid - 1] as u16 + sorted[mid] as u16) / 2) as u8
+            } else {
+                sorted[mid]
+            }
+        }
+
+        // Records the completion within the learner's cohort, flagging it as late past the due date.
+        fn record_cohort_completion(learner: &T::AccountId, course_id: &T::Hash, lecture_id: &T::Hash) {
+            let cohort_id = match Self::cohort_members(course_id, learner) {
+                Some(cohort_id) => cohort_id,
+                None => return,
+            };
+            let cohort = match Self::cohorts(course_id, cohort_id) {
+                Some(cohort) if !cohort.closed => cohort,
+                _ => return,
+            };
+            let now = T::Time::now();
+            // Lectures studied ahead of the cohort schedule are not part of it.
+            if now < cohort.start {
+                return;
+            }
+            // Without a due date the lecture is due by the end of the cohort.
+            let due = Self::lecture_due_dates((*course_id, cohort_id, *lecture_id)).unwrap_or(cohort.end);
+            let completion = CohortCompletion::<T> {
+                completed_at: now,
+                late: now > due,
+            };
+            <CohortCompletions<T>>::insert((*course_id, cohort_id, learner.clone(), *lecture_id), completion);
+            Self::deposit_event(Event::CohortLectureCompleted(
+                learner.clone(), *course_id, cohort_id, *lecture_id, now > due,
+            ));
+        }
+
+        // Removes `learner` from its cohort of a course and frees the seat.
+        fn do_leave_cohort(learner: &T::AccountId, course_id: &T::Hash) -> DispatchResult {
+            let cohort_id = <CohortMembers<T>>::take(course_id, learner).ok_or(<Error<T>>::NotInCohort)?;
+            <Cohorts<T>>::mutate(course_id, cohort_id, |c| {
+                if let Some(cohort) = c {
+                    cohort.seats_taken = cohort.seats_taken.saturating_sub(1);
+                }
+            });
+
+            Self::deposit_event(Event::CohortLeft(learner.clone(), *course_id, cohort_id));
+            Ok(())
+        }
+
+        // Pays `amount` from the course reward pot, bounded by the learner cap and the pot balance.
+        fn pay_reward(learner: &T::AccountId, course_id: &T::Hash, amount: BalanceOf<T>) -> DispatchResult {
+            let plan = Self::reward_plans(course_id).ok_or(<Error<T>>::RewardPlanNotExist)?;
+            let pot = Self::reward_pot(course_id);
+            let claimed = Self::rewards_claimed(learner, course_id);
+            let amount = amount
+                .min(plan.learner_cap.saturating_sub(claimed))
+                .min(T::Currency::free_balance(&pot));
+            if amount.is_zero() {
+                return Ok(());
+            }
+            T::Currency::transfer(&pot, learner, amount, ExistenceRequirement::AllowDeath)?;
+            <RewardsClaimed<T>>::insert(learner, course_id, claimed.saturating_add(amount));
+            Self::deposit_event(Event::RewardPaid(learner.clone(), *course_id, amount));
+            Ok(())
+        }
+
+        // Ensures `origin` is either `T::RewardOrigin` or the signed owner of the course.
+        // Returns `true` for `T::RewardOrigin`.
+        fn ensure_owner_or_reward_origin(origin: OriginFor<T>, course_id: &T::Hash) -> Result<bool, DispatchError> {
+            ensure!(<Courses<T>>::contains_key(course_id), <Error<T>>::CourseNotExist);
+            match T::RewardOrigin::try_origin(origin) {
+                Ok(_) => Ok(true),
+                Err(origin) => {
+                    let sender = ensure_signed(origin)?;
+                    ensure!(Self::is_course_owner(course_id, &sender)?, <Error<T>>::NotCourseOwner);
+                    Ok(false)
+                }
+            }
+        }
+
         fn _random_hash(sender: &T::AccountId) -> T::Hash {
             let nonce = <Nonce<T>>::get();
             let seed = T::CourseRandomness::random_seed();
@@ -496,6 +4224,11 @@ pub mod pallet {
             }
         }
         pub fn generate_nft(sender: &T::AccountId) -> Result<u64, DispatchError> {
+            let class_id = Self::create_class(sender, TokenType::Transferable)?;
+            Self::mint_token(class_id, sender)
+        }
+        // Helper to create an NFT class.
+        pub fn create_class(owner: &T::AccountId, token_type: TokenType) -> Result<ClassId, DispatchError> {
             let class_id = NextClassId::<T>::try_mutate(|id| -> Result<ClassId, DispatchError> {
                 let current_id = *id;
                 *id = id.checked_add(One::one()).ok_or(Error::<T>::NoAvailableClassId)?;
@@ -503,9 +4236,14 @@ pub mod pallet {
             })?;
             let info = ClassInfo {
                 total_issuance: Default::default(),
-                owner: sender.clone(),
+                owner: owner.clone(),
+                token_type,
             };
             Classes::<T>::insert(class_id, info);
+            Ok(class_id)
+        }
+        // Helper to mint a token of an existing NFT class.
+        pub fn mint_token(class_id: ClassId, owner: &T::AccountId) -> Result<TokenId, DispatchError> {
             NextTokenId::<T>::try_mutate(class_id, |id| -> Result<TokenId, DispatchError> {
                 let token_id = *id;
                 *id = id.checked_add(One::one()).ok_or(Error::<T>::NoAvailableTokenId)?;
@@ -520,13 +4258,59 @@ pub mod pallet {
                 })?;
 
                 let token_info = TokenInfo {
-                    owner: sender.clone(),
+                    owner: owner.clone(),
                 };
                 Tokens::<T>::insert(class_id, token_id, token_info);
-                TokensByOwner::<T>::insert((sender.clone(), class_id, token_id), ());
+                TokensByOwner::<T>::insert((owner.clone(), class_id, token_id), ());
                 Ok(token_id)
             })
         }
+        // Checks the token exists, is owned by `who` and belongs to a `Transferable` class.
+        fn ensure_transferable_by(class_id: ClassId, token_id: TokenId, who: &T::AccountId) -> DispatchResult {
+            let class = Self::classes(class_id).ok_or(<Error<T>>::ClassNotFound)?;
+            ensure!(class.token_type == TokenType::Transferable, <Error<T>>::TokenNotTransferable);
+            let token = Self::tokens(class_id, token_id).ok_or(<Error<T>>::TokenNotFound)?;
+            ensure!(token.owner == *who, <Error<T>>::NotTokenOwner);
+            Ok(())
+        }
+
+        // Moves a token to `to`, resetting its listing.
+        pub(crate) fn transfer_token_to(class_id: ClassId, token_id: TokenId, from: &T::AccountId, to: &T::AccountId) -> DispatchResult {
+            Tokens::<T>::try_mutate(class_id, token_id, |token| -> DispatchResult {
+                let info = token.as_mut().ok_or(<Error<T>>::TokenNotFound)?;
+                info.owner = to.clone();
+                Ok(())
+            })?;
+            TokensByOwner::<T>::remove((from.clone(), class_id, token_id));
+            TokensByOwner::<T>::insert((to.clone(), class_id, token_id), ());
+            <Listings<T>>::remove(class_id, token_id);
+
+            Self::deposit_event(Event::TokenTransferred(from.clone(), to.clone(), class_id, token_id));
+            Ok(())
+        }
+
+        /// Pays a token sale from `buyer`: the `royalty` to its beneficiary, the rest to `seller`.
+        ///
+        /// Every in-pallet sale of a token settles through this helper.
+        pub fn settle_token_sale(
+            class_id: ClassId,
+            token_id: TokenId,
+            buyer: &T::AccountId,
+            seller: &T::AccountId,
+            price: BalanceOf<T>,
+            royalty: Option<ClassRoyalty<T::AccountId>>,
+        ) -> DispatchResult {
+            let mut seller_share = price;
+            if let Some(royalty) = royalty {
+                let amount = royalty.rate * price;
+                if !amount.is_zero() && royalty.beneficiary != *seller {
+                    T::Currency::transfer(buyer, &royalty.beneficiary, amount, ExistenceRequirement::KeepAlive)?;
+                    seller_share = price.saturating_sub(amount);
+                    Self::deposit_event(Event::TokenRoyaltyPaid(royalty.beneficiary, class_id, token_id, amount));
+                }
+            }
+            T::Currency::transfer(buyer, seller, seller_share, ExistenceRequirement::KeepAlive)
+        }
         // ACTION #1b
         pub fn is_course_owner(course_id: &T::Hash, acct: &T::AccountId) -> Result<bool, Error<T>> {
             match Self::courses(course_id) {
@@ -0,0 +1,304 @@
+//! Storage migrations for the courses pallet.
+
+/// Version 1: Course and Lecture ids are derived from the id nonce instead of their content.
+///
+/// Existing ids are kept, they remain valid keys. Content-derived ids let a second identical
+/// course overwrite the first one while its id was pushed twice to `CoursesOwned`, so owned
+/// lists are deduplicated and `CourseCnt` is recomputed from the stored courses.
+pub mod v1 {
+    use crate::pallet::{Config, CourseCnt, Courses, CoursesOwned, Pallet};
+    use codec::Decode;
+    use frame_support::{
+        storage::unhashed,
+        traits::{Get, GetStorageVersion, StorageVersion},
+        weights::Weight,
+        BoundedVec,
+    };
+    use sp_std::{convert::TryFrom, prelude::*};
+
+    /// Leading fields of `Course`, which later versions only extend.
+    #[derive(Decode)]
+    struct CoursePrefix<AccountId> {
+        _name: Vec<u8>,
+        owner: AccountId,
+    }
+
+    // Owner of a course, decoded without depending on the current `Course` layout.
+    fn course_owner<T: Config>(course_id: &T::Hash) -> Option<T::AccountId> {
+        unhashed::get::<CoursePrefix<T::AccountId>>(&<Courses<T>>::hashed_key_for(course_id))
+            .map(|prefix| prefix.owner)
+    }
+
+    pub fn migrate<T: Config>() -> Weight {
+        if Pallet::<T>::on_chain_storage_version() >= 1 {
+            return 0;
+        }
+
+        let mut reads: Weight = 0;
+        let mut writes: Weight = 0;
+        <CoursesOwned<T>>::translate::<BoundedVec<T::Hash, T::MaxCourseOwned>, _>(|owner, owned| {
+            reads += 1;
+            writes += 1;
+            let mut repaired: Vec<T::Hash> = Vec::with_capacity(owned.len());
+            for course_id in owned.into_inner() {
+                reads += 1;
+                let owned_by = course_owner::<T>(&course_id).map_or(false, |o| o == owner);
+                if owned_by && !repaired.contains(&course_id) {
+                    repaired.push(course_id);
+                }
+            }
+            // Deduplicating only shrinks the list, so it always fits the bound.
+            BoundedVec::try_from(repaired).ok()
+        });
+
+        let count = <Courses<T>>::iter_keys().count() as u64;
+        reads += count;
+        <CourseCnt<T>>::put(count);
+        StorageVersion::new(1).put::<Pallet<T>>();
+        writes += 2;
+
+        log::info!("Courses migrated to storage version 1, {} courses.", count);
+        T::DbWeight::get().reads_writes(reads, writes)
+    }
+}
+
+/// Version 2: `Course` gains a price, its payment currency, a license and a reference to the
+/// course it was forked from.
+///
+/// Existing courses are free, priced in the native token, keep all rights reserved and have
+/// no parent.
+pub mod v2 {
+    use crate::pallet::{Config, Course, Courses, License, Pallet, PaymentCurrency};
+    use codec::Decode;
+    use frame_support::{
+        traits::{Get, GetStorageVersion, StorageVersion},
+        weights::Weight,
+    };
+    use sp_std::prelude::*;
+
+    /// `Course` as stored before version 2.
+    #[derive(Decode)]
+    struct OldCourse<AccountId> {
+        name: Vec<u8>,
+        owner: AccountId,
+        image_url: Vec<u8>,
+        category: Vec<u8>,
+        description: Vec<u8>,
+        live: bool,
+    }
+
+    pub fn migrate<T: Config>() -> Weight {
+        if Pallet::<T>::on_chain_storage_version() >= 2 {
+            return 0;
+        }
+
+        let mut translated: Weight = 0;
+        <Courses<T>>::translate::<OldCourse<T::AccountId>, _>(|_, old| {
+            translated += 1;
+            Some(Course::<T> {
+                name: old.name,
+                owner: old.owner,
+                image_url: old.image_url,
+                category: old.category,
+                description: old.description,
+                live: old.live,
+                price: None,
+                currency: PaymentCurrency::Native,
+                license: License::AllRightsReserved,
+                parent: None,
+                parent_royalty: None,
+            })
+        });
+        StorageVersion::new(2).put::<Pallet<T>>();
+
+        log::info!("Courses migrated to storage version 2, {} courses.", translated);
+        T::DbWeight::get().reads_writes(translated, translated + 1)
+    }
+}
+
+/// Version 3: lectures, lecture completions and token classes gain the fields added with
+/// assignments, scheduled releases, editions and soulbound tokens.
+///
+/// Existing lectures are standard lectures released right away, existing completions were
+/// studied in edition 0, the edition of courses that never snapshotted their lectures, and
+/// existing classes stay transferable.
+pub mod v3 {
+    use crate::pallet::{
+        ClassInfo, Classes, Config, CourseLectureCount, LearnerLectureCount, Lecture, LectureCompleted,
+        LectureKind, Lectures, LecturesCompleted, Pallet, TokenId, TokenType,
+    };
+    use codec::Decode;
+    use frame_support::{
+        storage::StoragePrefixedMap,
+        traits::{Get, GetStorageVersion, StorageVersion},
+        weights::Weight,
+    };
+    use sp_std::prelude::*;
+
+    #[derive(Decode)]
+    struct OldLecture<AccountId> {
+        name: Vec<u8>,
+        contents: Vec<u8>,
+        owner: AccountId,
+    }
+
+    #[derive(Decode)]
+    struct OldLectureCompleted<AccountId> {
+        owner: AccountId,
+    }
+
+    #[derive(Decode)]
+    struct OldClassInfo<AccountId> {
+        total_issuance: TokenId,
+        owner: AccountId,
+    }
+
+    pub fn migrate<T: Config>() -> Weight {
+        if Pallet::<T>::on_chain_storage_version() >= 3 {
+            return 0;
+        }
+
+        let mut translated: Weight = 0;
+        <Lectures<T> as StoragePrefixedMap<Lecture<T>>>::translate_values::<OldLecture<T::AccountId>, _>(|old| {
+            translated += 1;
+            Some(Lecture::<T> {
+                name: old.name,
+                contents: old.contents,
+                owner: old.owner,
+                kind: LectureKind::Standard,
+                release: None,
+            })
+        });
+        <LecturesCompleted<T> as StoragePrefixedMap<LectureCompleted<T>>>::translate_values::<
+            OldLectureCompleted<T::AccountId>,
+            _,
+        >(|old| {
+            translated += 1;
+            Some(LectureCompleted::<T> { owner: old.owner, edition: 0 })
+        });
+        // Badge rules count the lectures completed before the counters existed.
+        for (learner, course_id, _) in <LecturesCompleted<T>>::iter_keys() {
+            <LearnerLectureCount<T>>::mutate(&learner, |count| *count = count.saturating_add(1));
+            <CourseLectureCount<T>>::mutate(&learner, &course_id, |count| *count = count.saturating_add(1));
+            translated += 1;
+        }
+        <Classes<T> as StoragePrefixedMap<ClassInfo<T::AccountId, TokenId>>>::translate_values::<
+            OldClassInfo<T::AccountId>,
+            _,
+        >(|old| {
+            translated += 1;
+            Some(ClassInfo {
+                total_issuance: old.total_issuance,
+                owner: old.owner,
+                token_type: TokenType::Transferable,
+            })
+        });
+        StorageVersion::new(3).put::<Pallet<T>>();
+
+        log::info!("Courses migrated to storage version 3, {} values.", translated);
+        T::DbWeight::get().reads_writes(translated, translated + 1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        mock::*,
+        pallet::{
+            ClassInfo, Classes, Course, CourseLectureCount, Courses, LearnerLectureCount, Lecture,
+            LectureCompleted, LectureKind, Lectures, LecturesCompleted, License, PaymentCurrency, TokenType,
+        },
+    };
+    use codec::Encode;
+    use frame_support::{
+        storage::unhashed,
+        traits::{GetStorageVersion, StorageVersion},
+    };
+    use sp_core::H256;
+
+    /// `Course` as stored by the baseline runtime.
+    #[derive(Encode)]
+    struct BaselineCourse {
+        name: Vec<u8>,
+        owner: u64,
+        image_url: Vec<u8>,
+        category: Vec<u8>,
+        description: Vec<u8>,
+        live: bool,
+    }
+
+    #[test]
+    fn v2_migrates_baseline_courses() {
+        new_test_ext().execute_with(|| {
+            let course_id = H256::repeat_byte(1);
+            let baseline = BaselineCourse {
+                name: b"Rust".to_vec(),
+                owner: 1,
+                image_url: b"ipfs://image".to_vec(),
+                category: b"programming".to_vec(),
+                description: b"Learn Rust".to_vec(),
+                live: true,
+            };
+            unhashed::put(&<Courses<Test>>::hashed_key_for(course_id), &baseline);
+            StorageVersion::new(1).put::<CoursesModule>();
+
+            super::v2::migrate::<Test>();
+
+            assert_eq!(
+                <Courses<Test>>::get(course_id),
+                Some(Course::<Test> {
+                    name: baseline.name,
+                    owner: 1,
+                    image_url: baseline.image_url,
+                    category: baseline.category,
+                    description: baseline.description,
+                    live: true,
+                    price: None,
+                    currency: PaymentCurrency::Native,
+                    license: License::AllRightsReserved,
+                    parent: None,
+                    parent_royalty: None,
+                })
+            );
+            assert_eq!(CoursesModule::on_chain_storage_version(), 2);
+        });
+    }
+
+    #[test]
+    fn v3_migrates_baseline_lectures_completions_and_classes() {
+        new_test_ext().execute_with(|| {
+            let course_id = H256::repeat_byte(1);
+            let lecture_id = H256::repeat_byte(2);
+            // Baseline `Lecture`, `LectureCompleted` and `ClassInfo` layouts.
+            let lecture = (b"Ownership".to_vec(), b"ipfs://lecture".to_vec(), 1u64);
+            unhashed::put(&<Lectures<Test>>::hashed_key_for(course_id, lecture_id), &lecture);
+            unhashed::put(&<LecturesCompleted<Test>>::hashed_key_for((2u64, course_id, lecture_id)), &2u64);
+            unhashed::put(&<Classes<Test>>::hashed_key_for(0u32), &(5u64, 1u64));
+            StorageVersion::new(2).put::<CoursesModule>();
+
+            super::v3::migrate::<Test>();
+
+            assert_eq!(
+                <Lectures<Test>>::get(course_id, lecture_id),
+                Some(Lecture::<Test> {
+                    name: lecture.0,
+                    contents: lecture.1,
+                    owner: 1,
+                    kind: LectureKind::Standard,
+                    release: None,
+                })
+            );
+            assert_eq!(
+                <LecturesCompleted<Test>>::get((2u64, course_id, lecture_id)),
+                Some(LectureCompleted::<Test> { owner: 2, edition: 0 })
+            );
+            assert_eq!(<LearnerLectureCount<Test>>::get(2u64), 1);
+            assert_eq!(<CourseLectureCount<Test>>::get(2u64, course_id), 1);
+            assert_eq!(
+                <Classes<Test>>::get(0u32),
+                Some(ClassInfo { total_issuance: 5, owner: 1, token_type: TokenType::Transferable })
+            );
+            assert_eq!(CoursesModule::on_chain_storage_version(), 3);
+        });
+    }
+}
@@ -0,0 +1,213 @@
+//! Test runtime for the courses pallet.
+
+use crate as pallet_courses;
+use frame_support::{
+    parameter_types,
+    traits::{Everything, GenesisBuild},
+    PalletId,
+};
+use frame_system::EnsureRoot;
+use sp_core::H256;
+use sp_runtime::{
+    testing::Header,
+    traits::{BlakeTwo256, IdentityLookup},
+    Perbill,
+};
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+frame_support::construct_runtime!(
+    pub enum Test where
+        Block = Block,
+        NodeBlock = Block,
+        UncheckedExtrinsic = UncheckedExtrinsic,
+    {
+        System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+        RandomnessCollectiveFlip: pallet_randomness_collective_flip::{Pallet, Storage},
+        Timestamp: pallet_timestamp::{Pallet, Call, Storage, Inherent},
+        Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
+        Assets: pallet_assets::{Pallet, Call, Storage, Event<T>},
+        CoursesModule: pallet_courses::{Pallet, Call, Storage, Config<T>, Event<T>},
+    }
+);
+
+parameter_types! {
+    pub const BlockHashCount: u64 = 250;
+    pub const SS58Prefix: u8 = 42;
+}
+
+impl frame_system::Config for Test {
+    type BaseCallFilter = Everything;
+    type BlockWeights = ();
+    type BlockLength = ();
+    type DbWeight = ();
+    type Origin = Origin;
+    type Call = Call;
+    type Index = u64;
+    type BlockNumber = u64;
+    type Hash = H256;
+    type Hashing = BlakeTwo256;
+    type AccountId = u64;
+    type Lookup = IdentityLookup<Self::AccountId>;
+    type Header = Header;
+    type Event = Event;
+    type BlockHashCount = BlockHashCount;
+    type Version = ();
+    type PalletInfo = PalletInfo;
+    type AccountData = pallet_balances::AccountData<u64>;
+    type OnNewAccount = ();
+    type OnKilledAccount = ();
+    type SystemWeightInfo = ();
+    type SS58Prefix = SS58Prefix;
+    type OnSetCode = ();
+}
+
+impl pallet_randomness_collective_flip::Config for Test {}
+
+parameter_types! {
+    pub const MinimumPeriod: u64 = 1;
+}
+
+impl pallet_timestamp::Config for Test {
+    type Moment = u64;
+    type OnTimestampSet = ();
+    type MinimumPeriod = MinimumPeriod;
+    type WeightInfo = ();
+}
+
+parameter_types! {
+    pub const ExistentialDeposit: u64 = 1;
+}
+
+impl pallet_balances::Config for Test {
+    type MaxLocks = ();
+    type MaxReserves = ();
+    type ReserveIdentifier = [u8; 8];
+    type Balance = u64;
+    type Event = Event;
+    type DustRemoval = ();
+    type ExistentialDeposit = ExistentialDeposit;
+    type AccountStore = System;
+    type WeightInfo = ();
+}
+
+parameter_types! {
+    pub const AssetDeposit: u64 = 1;
+    pub const ApprovalDeposit: u64 = 1;
+    pub const StringLimit: u32 = 50;
+    pub const MetadataDepositBase: u64 = 1;
+    pub const MetadataDepositPerByte: u64 = 1;
+}
+
+impl pallet_assets::Config for Test {
+    type Event = Event;
+    type Balance = u64;
+    type AssetId = u32;
+    type Currency = Balances;
+    type ForceOrigin = EnsureRoot<u64>;
+    type AssetDeposit = AssetDeposit;
+    type MetadataDepositBase = MetadataDepositBase;
+    type MetadataDepositPerByte = MetadataDepositPerByte;
+    type ApprovalDeposit = ApprovalDeposit;
+    type StringLimit = StringLimit;
+    type Freezer = ();
+    type Extra = ();
+    type WeightInfo = ();
+}
+
+parameter_types! {
+    pub const MaxCourseOwned: u32 = 100;
+    pub const CoursesPalletId: PalletId = PalletId(*b"py/crses");
+    pub const CourseBond: u64 = 10;
+    pub const ComplaintDeposit: u64 = 1;
+    pub const MaxComplaints: u32 = 4;
+    pub const ComplaintPeriod: u64 = 20;
+    pub const MaxReviewers: u32 = 3;
+    pub const MaxReviewerPool: u32 = 16;
+    pub const PassingScore: u8 = 60;
+    pub const ReviewTolerance: u8 = 10;
+    pub const ReviewPeriod: u64 = 10;
+    pub const MaxPathCourses: u32 = 8;
+    pub const MaxLearnerPaths: u32 = 4;
+    pub const MaxCouponRedemptions: u32 = 8;
+    pub const MaxPrerequisites: u32 = 4;
+    pub const MaxEditionLectures: u32 = 32;
+    pub const MaxWrappedKeyLen: u32 = 64;
+    pub const MaxBulkLectures: u32 = 16;
+    pub const MaxLanguageTagLen: u32 = 16;
+    pub const MaxTranslations: u32 = 4;
+    pub const MaxBadgeRules: u32 = 8;
+    pub const DayLength: u64 = 1_000;
+    pub const MaxLeaderboardSize: u32 = 3;
+    pub const MaxClassMetadata: u32 = 64;
+    pub const MaxTokenMetadata: u32 = 64;
+    pub const MaxClassRoyalty: Perbill = Perbill::from_percent(25);
+}
+
+parameter_types! {
+    pub static RequireVerifiedInstructor: bool = false;
+    pub static VerifiedInstructors: Vec<u64> = vec![];
+}
+
+// Verifies the accounts listed in `VerifiedInstructors`.
+pub struct TestInstructorVerifier;
+impl pallet_courses::InstructorVerifier<u64> for TestInstructorVerifier {
+    fn is_verified(who: &u64) -> bool {
+        VerifiedInstructors::get().contains(who)
+    }
+}
+
+impl pallet_courses::Config for Test {
+    type Event = Event;
+    type Currency = Balances;
+    type MaxCourseOwned = MaxCourseOwned;
+    type CourseRandomness = RandomnessCollectiveFlip;
+    type PalletId = CoursesPalletId;
+    type RewardOrigin = EnsureRoot<u64>;
+    type CourseBond = CourseBond;
+    type TakedownOrigin = EnsureRoot<u64>;
+    type ComplaintDeposit = ComplaintDeposit;
+    type MaxComplaints = MaxComplaints;
+    type ComplaintPeriod = ComplaintPeriod;
+    type Slashed = ();
+    type Time = Timestamp;
+    type MaxReviewers = MaxReviewers;
+    type MaxReviewerPool = MaxReviewerPool;
+    type PassingScore = PassingScore;
+    type ReviewTolerance = ReviewTolerance;
+    type ReviewPeriod = ReviewPeriod;
+    type MaxPathCourses = MaxPathCourses;
+    type MaxLearnerPaths = MaxLearnerPaths;
+    type MaxCouponRedemptions = MaxCouponRedemptions;
+    type MaxPrerequisites = MaxPrerequisites;
+    type MaxEditionLectures = MaxEditionLectures;
+    type MaxWrappedKeyLen = MaxWrappedKeyLen;
+    type MaxBulkLectures = MaxBulkLectures;
+    type MaxLanguageTagLen = MaxLanguageTagLen;
+    type MaxTranslations = MaxTranslations;
+    type MaxBadgeRules = MaxBadgeRules;
+    type DayLength = DayLength;
+    type MaxLeaderboardSize = MaxLeaderboardSize;
+    type InstructorVerifier = TestInstructorVerifier;
+    type RequireVerifiedInstructor = RequireVerifiedInstructor;
+    type AssetId = u32;
+    type Assets = Assets;
+    type MaxClassMetadata = MaxClassMetadata;
+    type MaxTokenMetadata = MaxTokenMetadata;
+    type MaxClassRoyalty = MaxClassRoyalty;
+}
+
+// Build genesis storage according to the mock runtime.
+pub fn new_test_ext() -> sp_io::TestExternalities {
+    let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+    pallet_balances::GenesisConfig::<Test> {
+        balances: vec![(1, 1_000), (2, 1_000), (3, 1_000), (4, 1_000), (5, 1_000)],
+    }
+    .assimilate_storage(&mut t)
+    .unwrap();
+    let mut ext = sp_io::TestExternalities::new(t);
+    // Events are only deposited from the first block on.
+    ext.execute_with(|| System::set_block_number(1));
+    ext
+}
@@ -0,0 +1,2076 @@
+//! Unit tests for the courses pallet.
+
+use crate::{
+    mock::*,
+    pallet::{
+        BadgeCondition, ClassRoyalty, Discount, Error, GenesisClass, GenesisConfig, GenesisCourse, GenesisLecture,
+        LeaderboardKind, LectureInput, LectureKind, License, PaymentCurrency, ReleaseSchedule, SectionInput, Streak,
+        TokenType, TokensByOwner,
+    },
+};
+use codec::Encode;
+use frame_support::{
+    assert_noop, assert_ok,
+    traits::{
+        tokens::nonfungibles::{Create, Inspect, InspectEnumerable, Mutate, Transfer},
+        GenesisBuild,
+    },
+};
+use sp_core::H256;
+use sp_runtime::{
+    traits::{BlakeTwo256, Hash},
+    DispatchError, Perbill, Percent,
+};
+
+const OWNER: u64 = 1;
+const LEARNER: u64 = 2;
+const REPORTER: u64 = 3;
+const FUNDER: u64 = 4;
+
+fn create_course(owner: u64) -> H256 {
+    assert_ok!(CoursesModule::create_course(
+        Origin::signed(owner),
+        b"Rust".to_vec(),
+        b"programming".to_vec(),
+        b"ipfs://image".to_vec(),
+        b"Learn Rust".to_vec(),
+    ));
+    *CoursesModule::courses_owned(owner).last().expect("course was just created")
+}
+
+// Lecture ids are derived from the nonce the pallet is about to take.
+fn create_lecture(owner: u64, course_id: H256) -> H256 {
+    let nonce = CoursesModule::get_nonce();
+    assert_ok!(CoursesModule::create_lecture(
+        Origin::signed(owner),
+        course_id,
+        b"Lecture".to_vec(),
+        nonce.encode(),
+    ));
+    BlakeTwo256::hash_of(&(b"lecture", course_id, nonce))
+}
+
+fn create_assignment(owner: u64, course_id: H256, reviewers: u8) -> H256 {
+    let nonce = CoursesModule::get_nonce();
+    assert_ok!(CoursesModule::create_assignment(
+        Origin::signed(owner),
+        course_id,
+        b"Assignment".to_vec(),
+        nonce.encode(),
+        reviewers,
+    ));
+    BlakeTwo256::hash_of(&(b"lecture", course_id, nonce))
+}
+
+// A published course with `lectures` standard lectures, priced at `price` if any.
+fn published_course(owner: u64, lectures: usize, price: Option<u64>) -> (H256, Vec<H256>) {
+    let course_id = create_course(owner);
+    let lecture_ids = (0..lectures).map(|_| create_lecture(owner, course_id)).collect();
+    if price.is_some() {
+        assert_ok!(CoursesModule::set_price(Origin::signed(owner), course_id, price, PaymentCurrency::Native));
+    }
+    assert_ok!(CoursesModule::publish_course(Origin::signed(owner), course_id));
+    (course_id, lecture_ids)
+}
+
+// A published course priced at `price` in `currency`.
+fn priced_course(owner: u64, lectures: usize, price: u64, currency: PaymentCurrency<u32>) -> (H256, Vec<H256>) {
+    let course_id = create_course(owner);
+    let lecture_ids = (0..lectures).map(|_| create_lecture(owner, course_id)).collect();
+    assert_ok!(CoursesModule::set_price(Origin::signed(owner), course_id, Some(price), currency));
+    assert_ok!(CoursesModule::publish_course(Origin::signed(owner), course_id));
+    (course_id, lecture_ids)
+}
+
+// Creates asset `id` and endows `accounts` with `amount` of it.
+fn create_asset(id: u32, accounts: &[u64], amount: u64) {
+    assert_ok!(Assets::force_create(Origin::root(), id, OWNER, true, 1));
+    for account in accounts {
+        assert_ok!(Assets::mint(Origin::signed(OWNER), id, *account, amount));
+    }
+}
+
+fn complete_all(learner: u64, course_id: H256, lecture_ids: &[H256]) {
+    for lecture_id in lecture_ids {
+        assert_ok!(CoursesModule::complete_lecture(Origin::signed(learner), course_id, *lecture_id));
+    }
+}
+
+fn create_encrypted_lecture(owner: u64, course_id: H256) -> H256 {
+    let nonce = CoursesModule::get_nonce();
+    assert_ok!(CoursesModule::create_encrypted_lecture(
+        Origin::signed(owner),
+        course_id,
+        b"Encrypted".to_vec(),
+        b"ciphertext".to_vec(),
+        BlakeTwo256::hash_of(b"content key"),
+    ));
+    BlakeTwo256::hash_of(&(b"lecture", course_id, nonce))
+}
+
+fn section(name: &[u8], lectures: usize) -> SectionInput {
+    SectionInput {
+        name: name.to_vec(),
+        lectures: (0..lectures)
+            .map(|ind| LectureInput {
+                name: b"Lecture".to_vec(),
+                contents: vec![ind as u8],
+                kind: LectureKind::Standard,
+            })
+            .collect(),
+    }
+}
+
+// Approves `reviewers` for the course and adds them to its reviewer pool.
+fn fill_reviewer_pool(owner: u64, course_id: H256, reviewers: &[u64]) {
+    for reviewer in reviewers {
+        assert_ok!(CoursesModule::approve_reviewer(Origin::signed(owner), course_id, *reviewer));
+        assert_ok!(CoursesModule::join_reviewer_pool(Origin::signed(*reviewer), course_id));
+    }
+}
+
+#[test]
+fn rewards_are_paid_up_to_the_learner_cap() {
+    new_test_ext().execute_with(|| {
+        let (course_id, lectures) = published_course(OWNER, 3, Some(50));
+        assert_ok!(CoursesModule::set_reward_plan(Origin::root(), course_id, 5, 20, 25));
+        assert_ok!(CoursesModule::fund_rewards(Origin::signed(FUNDER), course_id, 100));
+        assert_ok!(CoursesModule::enroll(Origin::signed(LEARNER), course_id, None, None));
+
+        complete_all(LEARNER, course_id, &lectures);
+
+        // Three lectures at 5, then the completion bonus is cut down to what is left of the cap.
+        assert_eq!(CoursesModule::rewards_claimed(LEARNER, course_id), 25);
+        assert_eq!(Balances::free_balance(LEARNER), 1_000 - 50 + 25);
+        assert_eq!(Balances::free_balance(CoursesModule::reward_pot(&course_id)), 75);
+        assert_eq!(Balances::free_balance(OWNER), 1_000 - 10 + 50);
+    });
+}
+
+#[test]
+fn rewards_are_bounded_by_the_pot() {
+    new_test_ext().execute_with(|| {
+        let (course_id, lectures) = published_course(OWNER, 2, Some(50));
+        assert_ok!(CoursesModule::set_reward_plan(Origin::root(), course_id, 5, 20, 100));
+        assert_ok!(CoursesModule::fund_rewards(Origin::signed(FUNDER), course_id, 8));
+        assert_ok!(CoursesModule::enroll(Origin::signed(LEARNER), course_id, None, None));
+
+        complete_all(LEARNER, course_id, &lectures);
+
+        assert_eq!(CoursesModule::rewards_claimed(LEARNER, course_id), 8);
+        assert_eq!(Balances::free_balance(CoursesModule::reward_pot(&course_id)), 0);
+    });
+}
+
+#[test]
+fn unpaid_completions_are_not_rewarded() {
+    new_test_ext().execute_with(|| {
+        let (course_id, lectures) = published_course(OWNER, 2, None);
+        assert_ok!(CoursesModule::set_reward_plan(Origin::root(), course_id, 5, 20, 100));
+        assert_ok!(CoursesModule::fund_rewards(Origin::signed(FUNDER), course_id, 100));
+
+        complete_all(LEARNER, course_id, &lectures);
+        complete_all(OWNER, course_id, &lectures);
+
+        assert_eq!(CoursesModule::rewards_claimed(LEARNER, course_id), 0);
+        assert_eq!(CoursesModule::rewards_claimed(OWNER, course_id), 0);
+        assert_eq!(Balances::free_balance(CoursesModule::reward_pot(&course_id)), 100);
+    });
+}
+
+#[test]
+fn lectures_are_rewarded_once_across_editions() {
+    new_test_ext().execute_with(|| {
+        let (course_id, lectures) = published_course(OWNER, 2, Some(50));
+        assert_ok!(CoursesModule::set_reward_plan(Origin::root(), course_id, 5, 0, 100));
+        assert_ok!(CoursesModule::fund_rewards(Origin::signed(FUNDER), course_id, 100));
+        assert_ok!(CoursesModule::enroll(Origin::signed(LEARNER), course_id, None, None));
+        assert_ok!(CoursesModule::complete_lecture(Origin::signed(LEARNER), course_id, lectures[0]));
+        assert_eq!(CoursesModule::rewards_claimed(LEARNER, course_id), 5);
+
+        assert_ok!(CoursesModule::update_lecture(
+            Origin::signed(OWNER),
+            course_id,
+            lectures[0],
+            b"Lecture".to_vec(),
+            b"Revised".to_vec(),
+        ));
+        assert_ok!(CoursesModule::publish_edition(Origin::signed(OWNER), course_id));
+        assert_ok!(CoursesModule::migrate_progress(Origin::signed(LEARNER), course_id));
+        assert_ok!(CoursesModule::complete_lecture(Origin::signed(LEARNER), course_id, lectures[0]));
+
+        assert_eq!(CoursesModule::rewards_claimed(LEARNER, course_id), 5);
+    });
+}
+
+#[test]
+fn funded_reward_plans_can_only_be_lowered_by_the_owner() {
+    new_test_ext().execute_with(|| {
+        let course_id = create_course(OWNER);
+        assert_ok!(CoursesModule::set_reward_plan(Origin::signed(OWNER), course_id, 5, 20, 50));
+        assert_ok!(CoursesModule::fund_rewards(Origin::signed(FUNDER), course_id, 100));
+
+        assert_noop!(
+            CoursesModule::set_reward_plan(Origin::signed(OWNER), course_id, 6, 20, 50),
+            Error::<Test>::RewardPlanLocked
+        );
+        assert_ok!(CoursesModule::set_reward_plan(Origin::signed(OWNER), course_id, 4, 20, 50));
+        assert_ok!(CoursesModule::set_reward_plan(Origin::root(), course_id, 10, 20, 50));
+
+        // Removing the plan does not unlock the pot for a new, richer one.
+        assert_ok!(CoursesModule::remove_reward_plan(Origin::signed(OWNER), course_id));
+        assert_noop!(
+            CoursesModule::set_reward_plan(Origin::signed(OWNER), course_id, 1, 1, 1),
+            Error::<Test>::RewardPlanLocked
+        );
+
+        assert_ok!(CoursesModule::sweep_rewards(Origin::signed(OWNER), course_id));
+        assert_eq!(Balances::free_balance(CoursesModule::reward_pot(&course_id)), 0);
+        assert_ok!(CoursesModule::set_reward_plan(Origin::signed(OWNER), course_id, 1, 1, 1));
+    });
+}
+
+#[test]
+fn reward_pot_cannot_be_swept_with_an_active_plan() {
+    new_test_ext().execute_with(|| {
+        let course_id = create_course(OWNER);
+        assert_ok!(CoursesModule::set_reward_plan(Origin::signed(OWNER), course_id, 5, 20, 50));
+        assert_ok!(CoursesModule::fund_rewards(Origin::signed(FUNDER), course_id, 100));
+
+        assert_noop!(
+            CoursesModule::sweep_rewards(Origin::signed(OWNER), course_id),
+            Error::<Test>::RewardPlanActive
+        );
+        assert_noop!(
+            CoursesModule::remove_reward_plan(Origin::signed(LEARNER), course_id),
+            Error::<Test>::NotCourseOwner
+        );
+    });
+}
+
+#[test]
+fn take_down_slashes_the_bond_and_refunds_complaints() {
+    new_test_ext().execute_with(|| {
+        let (course_id, _) = published_course(OWNER, 1, None);
+        assert_eq!(Balances::reserved_balance(OWNER), 10);
+        assert_ok!(CoursesModule::file_complaint(Origin::signed(LEARNER), course_id, H256::repeat_byte(1)));
+        assert_ok!(CoursesModule::file_complaint(Origin::signed(REPORTER), course_id, H256::repeat_byte(2)));
+        assert_eq!(Balances::reserved_balance(REPORTER), 1);
+
+        assert_noop!(CoursesModule::take_down_course(Origin::signed(REPORTER), course_id), DispatchError::BadOrigin);
+        assert_ok!(CoursesModule::take_down_course(Origin::root(), course_id));
+
+        assert_eq!(Balances::reserved_balance(OWNER), 0);
+        assert_eq!(Balances::free_balance(OWNER), 990);
+        for reporter in [LEARNER, REPORTER].iter() {
+            assert_eq!(Balances::reserved_balance(reporter), 0);
+            assert_eq!(Balances::free_balance(reporter), 1_000);
+        }
+        assert_eq!(CoursesModule::open_complaints(course_id), 0);
+        assert_eq!(CoursesModule::taken_down(course_id), Some(1));
+        assert!(!CoursesModule::courses(course_id).unwrap().live);
+
+        assert_noop!(
+            CoursesModule::publish_course(Origin::signed(OWNER), course_id),
+            Error::<Test>::CourseTakenDown
+        );
+        assert_noop!(
+            CoursesModule::fork_course(Origin::signed(OWNER), course_id),
+            Error::<Test>::CourseTakenDown
+        );
+    });
+}
+
+#[test]
+fn dismissed_complaints_slash_the_reporter() {
+    new_test_ext().execute_with(|| {
+        let (course_id, _) = published_course(OWNER, 1, None);
+        assert_ok!(CoursesModule::file_complaint(Origin::signed(REPORTER), course_id, H256::repeat_byte(1)));
+        assert_noop!(
+            CoursesModule::unpublish_course(Origin::signed(OWNER), course_id),
+            Error::<Test>::ComplaintsPending
+        );
+
+        assert_ok!(CoursesModule::dismiss_complaint(Origin::root(), course_id, 0));
+        assert_eq!(Balances::reserved_balance(REPORTER), 0);
+        assert_eq!(Balances::free_balance(REPORTER), 999);
+        assert_eq!(CoursesModule::open_complaints(course_id), 0);
+
+        // The bond is released in full once the course is unpublished.
+        assert_ok!(CoursesModule::unpublish_course(Origin::signed(OWNER), course_id));
+        assert_eq!(Balances::reserved_balance(OWNER), 0);
+        assert_eq!(Balances::free_balance(OWNER), 1_000);
+    });
+}
+
+#[test]
+fn stale_complaints_expire_with_a_refund() {
+    new_test_ext().execute_with(|| {
+        let (course_id, _) = published_course(OWNER, 1, None);
+        assert_ok!(CoursesModule::file_complaint(Origin::signed(REPORTER), course_id, H256::repeat_byte(1)));
+
+        System::set_block_number(20);
+        assert_noop!(
+            CoursesModule::expire_complaint(Origin::signed(LEARNER), course_id, 0),
+            Error::<Test>::ComplaintPeriodNotElapsed
+        );
+
+        System::set_block_number(21);
+        assert_ok!(CoursesModule::expire_complaint(Origin::signed(LEARNER), course_id, 0));
+        assert_eq!(Balances::reserved_balance(REPORTER), 0);
+        assert_eq!(Balances::free_balance(REPORTER), 1_000);
+        assert_eq!(CoursesModule::open_complaints(course_id), 0);
+        assert!(CoursesModule::complaints(course_id, 0).is_none());
+        assert_noop!(
+            CoursesModule::expire_complaint(Origin::signed(LEARNER), course_id, 0),
+            Error::<Test>::ComplaintNotExist
+        );
+    });
+}
+
+#[test]
+fn open_complaints_are_capped_per_course() {
+    new_test_ext().execute_with(|| {
+        let (course_id, _) = published_course(OWNER, 1, None);
+        for _ in 0..4 {
+            assert_ok!(CoursesModule::file_complaint(Origin::signed(REPORTER), course_id, H256::repeat_byte(1)));
+        }
+        assert_noop!(
+            CoursesModule::file_complaint(Origin::signed(REPORTER), course_id, H256::repeat_byte(1)),
+            Error::<Test>::TooManyComplaints
+        );
+        assert_eq!(Balances::reserved_balance(REPORTER), 4);
+
+        let unpublished = create_course(OWNER);
+        assert_noop!(
+            CoursesModule::file_complaint(Origin::signed(REPORTER), unpublished, H256::repeat_byte(1)),
+            Error::<Test>::CourseNotLive
+        );
+    });
+}
+
+#[test]
+fn cohorts_must_start_in_the_future_with_seats() {
+    new_test_ext().execute_with(|| {
+        let (course_id, _) = published_course(OWNER, 1, None);
+        Timestamp::set_timestamp(100);
+
+        assert_noop!(
+            CoursesModule::create_cohort(Origin::signed(OWNER), course_id, 50, 300, 1),
+            Error::<Test>::CohortStartInPast
+        );
+        assert_noop!(
+            CoursesModule::create_cohort(Origin::signed(OWNER), course_id, 300, 200, 1),
+            Error::<Test>::InvalidCohortSchedule
+        );
+        assert_noop!(
+            CoursesModule::create_cohort(Origin::signed(OWNER), course_id, 200, 300, 0),
+            Error::<Test>::NoCohortSeats
+        );
+        assert_noop!(
+            CoursesModule::create_cohort(Origin::signed(LEARNER), course_id, 200, 300, 1),
+            Error::<Test>::NotCourseOwner
+        );
+        assert_ok!(CoursesModule::create_cohort(Origin::signed(OWNER), course_id, 200, 300, 1));
+        assert_eq!(CoursesModule::next_cohort_id(course_id), 1);
+    });
+}
+
+#[test]
+fn cohort_seats_are_taken_until_the_start() {
+    new_test_ext().execute_with(|| {
+        let (course_id, _) = published_course(OWNER, 1, None);
+        Timestamp::set_timestamp(100);
+        assert_ok!(CoursesModule::create_cohort(Origin::signed(OWNER), course_id, 200, 300, 1));
+
+        assert_ok!(CoursesModule::join_cohort(Origin::signed(LEARNER), course_id, 0));
+        assert_noop!(
+            CoursesModule::join_cohort(Origin::signed(REPORTER), course_id, 0),
+            Error::<Test>::CohortFull
+        );
+        assert_ok!(CoursesModule::leave_cohort(Origin::signed(LEARNER), course_id));
+        assert_eq!(CoursesModule::cohorts(course_id, 0).unwrap().seats_taken, 0);
+        assert_ok!(CoursesModule::join_cohort(Origin::signed(REPORTER), course_id, 0));
+
+        assert_ok!(CoursesModule::remove_cohort_member(Origin::signed(OWNER), course_id, REPORTER));
+        Timestamp::set_timestamp(200);
+        assert_noop!(
+            CoursesModule::join_cohort(Origin::signed(LEARNER), course_id, 0),
+            Error::<Test>::CohortStarted
+        );
+    });
+}
+
+#[test]
+fn cohorts_of_paid_courses_require_enrollment() {
+    new_test_ext().execute_with(|| {
+        let (course_id, _) = published_course(OWNER, 1, Some(50));
+        Timestamp::set_timestamp(100);
+        assert_ok!(CoursesModule::create_cohort(Origin::signed(OWNER), course_id, 200, 300, 2));
+
+        assert_noop!(
+            CoursesModule::join_cohort(Origin::signed(LEARNER), course_id, 0),
+            Error::<Test>::NotEnrolled
+        );
+        assert_ok!(CoursesModule::enroll(Origin::signed(LEARNER), course_id, None, None));
+        assert_ok!(CoursesModule::join_cohort(Origin::signed(LEARNER), course_id, 0));
+    });
+}
+
+#[test]
+fn cohort_completions_are_flagged_late_past_the_due_date() {
+    new_test_ext().execute_with(|| {
+        let (course_id, lectures) = published_course(OWNER, 3, None);
+        Timestamp::set_timestamp(100);
+        assert_ok!(CoursesModule::create_cohort(Origin::signed(OWNER), course_id, 200, 300, 1));
+        assert_ok!(CoursesModule::set_lecture_due_date(Origin::signed(OWNER), course_id, 0, lectures[1], 250));
+        assert_ok!(CoursesModule::join_cohort(Origin::signed(LEARNER), course_id, 0));
+
+        // Studied ahead of the schedule, not part of the cohort.
+        assert_ok!(CoursesModule::complete_lecture(Origin::signed(LEARNER), course_id, lectures[0]));
+        assert!(CoursesModule::cohort_completions((course_id, 0, LEARNER, lectures[0])).is_none());
+
+        Timestamp::set_timestamp(260);
+        assert_ok!(CoursesModule::complete_lecture(Origin::signed(LEARNER), course_id, lectures[1]));
+        assert_ok!(CoursesModule::complete_lecture(Origin::signed(LEARNER), course_id, lectures[2]));
+        assert!(CoursesModule::cohort_completions((course_id, 0, LEARNER, lectures[1])).unwrap().late);
+        assert!(!CoursesModule::cohort_completions((course_id, 0, LEARNER, lectures[2])).unwrap().late);
+
+        assert_noop!(
+            CoursesModule::close_cohort(Origin::signed(OWNER), course_id, 0),
+            Error::<Test>::CohortNotEnded
+        );
+        Timestamp::set_timestamp(300);
+        assert_ok!(CoursesModule::close_cohort(Origin::signed(OWNER), course_id, 0));
+        assert!(CoursesModule::cohorts(course_id, 0).unwrap().closed);
+    });
+}
+
+#[test]
+fn reviewers_must_complete_the_course_or_be_approved() {
+    new_test_ext().execute_with(|| {
+        let (course_id, lectures) = published_course(OWNER, 1, None);
+        assert_noop!(
+            CoursesModule::join_reviewer_pool(Origin::signed(REPORTER), course_id),
+            Error::<Test>::NotQualifiedReviewer
+        );
+
+        complete_all(LEARNER, course_id, &lectures);
+        assert_ok!(CoursesModule::join_reviewer_pool(Origin::signed(LEARNER), course_id));
+        assert_noop!(
+            CoursesModule::join_reviewer_pool(Origin::signed(LEARNER), course_id),
+            Error::<Test>::AlreadyReviewer
+        );
+    });
+}
+
+#[test]
+fn passed_assignments_are_completed_and_rewarded() {
+    new_test_ext().execute_with(|| {
+        let course_id = create_course(OWNER);
+        let assignment = create_assignment(OWNER, course_id, 3);
+        assert_ok!(CoursesModule::publish_course(Origin::signed(OWNER), course_id));
+        assert_ok!(CoursesModule::set_reward_plan(Origin::root(), course_id, 5, 20, 100));
+        assert_ok!(CoursesModule::fund_rewards(Origin::signed(FUNDER), course_id, 100));
+        fill_reviewer_pool(OWNER, course_id, &[3, 4, 5]);
+
+        assert_noop!(
+            CoursesModule::complete_lecture(Origin::signed(LEARNER), course_id, assignment),
+            Error::<Test>::LectureRequiresReview
+        );
+        assert_ok!(CoursesModule::submit_assignment(Origin::signed(LEARNER), course_id, assignment, H256::repeat_byte(1)));
+        let reviewers = CoursesModule::submissions((course_id, assignment, LEARNER)).unwrap().reviewers;
+        assert_eq!(reviewers.len(), 3);
+        assert_noop!(
+            CoursesModule::review_assignment(Origin::signed(LEARNER), course_id, assignment, LEARNER, 100),
+            Error::<Test>::NotAssignedReviewer
+        );
+
+        for (reviewer, score) in [(3, 70), (4, 80), (5, 30)].iter() {
+            assert_ok!(CoursesModule::review_assignment(
+                Origin::signed(*reviewer),
+                course_id,
+                assignment,
+                LEARNER,
+                *score,
+            ));
+        }
+
+        assert!(CoursesModule::lectures_completed((LEARNER, course_id, assignment)).is_some());
+        assert!(CoursesModule::submissions((course_id, assignment, LEARNER)).is_none());
+        assert_eq!(CoursesModule::course_scores(course_id, LEARNER), 70);
+        assert_eq!(CoursesModule::reviewer_reputation(3).agreements, 1);
+        assert_eq!(CoursesModule::reviewer_reputation(5).reviews, 1);
+        assert_eq!(CoursesModule::reviewer_reputation(5).agreements, 0);
+        // Peer-graded lectures are rewarded, the completion bonus is kept for paying learners.
+        assert_eq!(CoursesModule::rewards_claimed(LEARNER, course_id), 5);
+    });
+}
+
+#[test]
+fn failed_assignments_can_be_submitted_again() {
+    new_test_ext().execute_with(|| {
+        let course_id = create_course(OWNER);
+        let assignment = create_assignment(OWNER, course_id, 3);
+        assert_ok!(CoursesModule::publish_course(Origin::signed(OWNER), course_id));
+        fill_reviewer_pool(OWNER, course_id, &[3, 4, 5]);
+        assert_ok!(CoursesModule::submit_assignment(Origin::signed(LEARNER), course_id, assignment, H256::repeat_byte(1)));
+        assert_noop!(
+            CoursesModule::submit_assignment(Origin::signed(LEARNER), course_id, assignment, H256::repeat_byte(2)),
+            Error::<Test>::SubmissionPending
+        );
+
+        for (reviewer, score) in [(3, 10), (4, 20), (5, 90)].iter() {
+            assert_ok!(CoursesModule::review_assignment(
+                Origin::signed(*reviewer),
+                course_id,
+                assignment,
+                LEARNER,
+                *score,
+            ));
+        }
+
+        assert!(CoursesModule::lectures_completed((LEARNER, course_id, assignment)).is_none());
+        assert_eq!(CoursesModule::course_scores(course_id, LEARNER), 0);
+        assert_ok!(CoursesModule::submit_assignment(Origin::signed(LEARNER), course_id, assignment, H256::repeat_byte(2)));
+    });
+}
+
+#[test]
+fn passed_assignments_are_kept_when_the_completion_fails() {
+    new_test_ext().execute_with(|| {
+        let course_id = create_course(OWNER);
+        let assignment = create_assignment(OWNER, course_id, 1);
+        assert_ok!(CoursesModule::publish_course(Origin::signed(OWNER), course_id));
+        fill_reviewer_pool(OWNER, course_id, &[REPORTER]);
+        assert_ok!(CoursesModule::submit_assignment(Origin::signed(LEARNER), course_id, assignment, H256::repeat_byte(1)));
+
+        // The course goes offline while the submission is being reviewed.
+        assert_ok!(CoursesModule::unpublish_course(Origin::signed(OWNER), course_id));
+        assert_ok!(CoursesModule::review_assignment(Origin::signed(REPORTER), course_id, assignment, LEARNER, 90));
+
+        System::assert_last_event(Event::CoursesModule(crate::Event::CompletionDeferred(
+            LEARNER, course_id, assignment,
+        )));
+        assert!(CoursesModule::passed_assignments((course_id, assignment, LEARNER)).is_some());
+        assert_eq!(CoursesModule::course_scores(course_id, LEARNER), 90);
+        assert!(CoursesModule::lectures_completed((LEARNER, course_id, assignment)).is_none());
+        assert_noop!(
+            CoursesModule::submit_assignment(Origin::signed(LEARNER), course_id, assignment, H256::repeat_byte(2)),
+            Error::<Test>::CourseNotPublished
+        );
+
+        assert_ok!(CoursesModule::publish_course(Origin::signed(OWNER), course_id));
+        assert_noop!(
+            CoursesModule::submit_assignment(Origin::signed(LEARNER), course_id, assignment, H256::repeat_byte(2)),
+            Error::<Test>::CompletionPending
+        );
+        assert_ok!(CoursesModule::complete_lecture(Origin::signed(LEARNER), course_id, assignment));
+        assert!(CoursesModule::passed_assignments((course_id, assignment, LEARNER)).is_none());
+        assert!(CoursesModule::lectures_completed((LEARNER, course_id, assignment)).is_some());
+    });
+}
+
+#[test]
+fn stalled_submissions_can_be_withdrawn_after_the_review_period() {
+    new_test_ext().execute_with(|| {
+        let course_id = create_course(OWNER);
+        let assignment = create_assignment(OWNER, course_id, 1);
+        assert_ok!(CoursesModule::publish_course(Origin::signed(OWNER), course_id));
+        fill_reviewer_pool(OWNER, course_id, &[REPORTER]);
+        assert_ok!(CoursesModule::submit_assignment(Origin::signed(LEARNER), course_id, assignment, H256::repeat_byte(1)));
+
+        assert_noop!(
+            CoursesModule::withdraw_submission(Origin::signed(LEARNER), course_id, assignment),
+            Error::<Test>::ReviewPeriodNotElapsed
+        );
+        System::set_block_number(11);
+        assert_ok!(CoursesModule::withdraw_submission(Origin::signed(LEARNER), course_id, assignment));
+        assert!(CoursesModule::submissions((course_id, assignment, LEARNER)).is_none());
+    });
+}
+
+#[test]
+fn course_completion_records_the_block_and_edition() {
+    new_test_ext().execute_with(|| {
+        let (course_id, lectures) = published_course(OWNER, 2, None);
+        complete_all(LEARNER, course_id, &lectures[..1]);
+        assert!(CoursesModule::course_completion(&LEARNER, &course_id).is_none());
+
+        System::set_block_number(5);
+        complete_all(LEARNER, course_id, &lectures[1..]);
+
+        let completion = CoursesModule::course_completion(&LEARNER, &course_id).unwrap();
+        assert_eq!(completion.owner, LEARNER);
+        assert_eq!(completion.completed_at, 5);
+        assert_eq!(completion.edition, 1);
+        assert_eq!(CoursesModule::completion_count(course_id), 1);
+    });
+}
+
+#[test]
+fn path_enrollment_charges_the_discounted_bundle() {
+    new_test_ext().execute_with(|| {
+        let (first, first_lectures) = priced_course(OWNER, 1, 100, PaymentCurrency::Native);
+        let (second, second_lectures) = priced_course(OWNER, 1, 50, PaymentCurrency::Native);
+        assert_ok!(CoursesModule::create_learning_path(
+            Origin::signed(OWNER),
+            b"Rust track".to_vec(),
+            vec![first, second],
+            Percent::from_percent(10),
+        ));
+
+        assert_ok!(CoursesModule::enroll_in_path(Origin::signed(LEARNER), 0));
+        assert_eq!(Balances::free_balance(LEARNER), 1_000 - 90 - 45);
+        assert_eq!(Balances::free_balance(OWNER), 1_000 - 20 + 90 + 45);
+        assert_eq!(CoursesModule::enrollments(LEARNER, first).unwrap().paid, 90);
+        assert_eq!(CoursesModule::learner_path_count(LEARNER), 1);
+        assert_noop!(
+            CoursesModule::enroll_in_path(Origin::signed(LEARNER), 0),
+            Error::<Test>::AlreadyEnrolledInPath
+        );
+
+        complete_all(LEARNER, first, &first_lectures);
+        assert!(CoursesModule::path_certificates(LEARNER, 0).is_none());
+        complete_all(LEARNER, second, &second_lectures);
+        let class_id = CoursesModule::learning_paths(0).unwrap().certificate_class;
+        let token_id = CoursesModule::path_certificates(LEARNER, 0).unwrap();
+        assert_eq!(CoursesModule::tokens(class_id, token_id).unwrap().owner, LEARNER);
+    });
+}
+
+#[test]
+fn path_enrollment_skips_courses_already_enrolled() {
+    new_test_ext().execute_with(|| {
+        let (first, _) = priced_course(OWNER, 1, 100, PaymentCurrency::Native);
+        let (second, _) = priced_course(OWNER, 1, 50, PaymentCurrency::Native);
+        assert_ok!(CoursesModule::create_learning_path(
+            Origin::signed(OWNER),
+            b"Rust track".to_vec(),
+            vec![first, second],
+            Percent::from_percent(10),
+        ));
+        assert_ok!(CoursesModule::enroll(Origin::signed(LEARNER), first, None, None));
+
+        assert_ok!(CoursesModule::enroll_in_path(Origin::signed(LEARNER), 0));
+        assert_eq!(Balances::free_balance(LEARNER), 1_000 - 100 - 45);
+        assert_eq!(CoursesModule::enrollments(LEARNER, first).unwrap().paid, 100);
+    });
+}
+
+#[test]
+fn path_enrollment_pays_in_the_asset_of_the_courses() {
+    new_test_ext().execute_with(|| {
+        create_asset(7, &[LEARNER], 500);
+        let (first, _) = priced_course(OWNER, 1, 100, PaymentCurrency::Asset(7));
+        let (second, _) = priced_course(OWNER, 1, 50, PaymentCurrency::Asset(7));
+        assert_ok!(CoursesModule::create_learning_path(
+            Origin::signed(OWNER),
+            b"Rust track".to_vec(),
+            vec![first, second],
+            Percent::from_percent(10),
+        ));
+
+        assert_ok!(CoursesModule::enroll_in_path(Origin::signed(LEARNER), 0));
+        assert_eq!(Assets::balance(7, LEARNER), 500 - 135);
+        assert_eq!(Assets::balance(7, OWNER), 135);
+        assert_eq!(Balances::free_balance(LEARNER), 1_000);
+    });
+}
+
+#[test]
+fn path_enrollment_rejects_mixed_currencies() {
+    new_test_ext().execute_with(|| {
+        create_asset(7, &[LEARNER], 500);
+        let (native, _) = priced_course(OWNER, 1, 100, PaymentCurrency::Native);
+        let (asset, _) = priced_course(OWNER, 1, 50, PaymentCurrency::Asset(7));
+        let (free, _) = published_course(OWNER, 1, None);
+        assert_ok!(CoursesModule::create_learning_path(
+            Origin::signed(OWNER),
+            b"Mixed".to_vec(),
+            vec![native, asset],
+            Percent::from_percent(10),
+        ));
+        assert_ok!(CoursesModule::create_learning_path(
+            Origin::signed(OWNER),
+            b"Native".to_vec(),
+            vec![free, native],
+            Percent::from_percent(10),
+        ));
+
+        assert_noop!(
+            CoursesModule::enroll_in_path(Origin::signed(LEARNER), 0),
+            Error::<Test>::MixedCurrencyPath
+        );
+        // Free courses are not charged, so they do not count as another currency.
+        assert_ok!(CoursesModule::enroll_in_path(Origin::signed(LEARNER), 1));
+        assert_eq!(Balances::free_balance(LEARNER), 1_000 - 90);
+        assert!(CoursesModule::enrollments(LEARNER, free).is_some());
+    });
+}
+
+#[test]
+fn path_enrollments_are_capped_per_learner() {
+    new_test_ext().execute_with(|| {
+        let (course_id, _) = published_course(OWNER, 1, None);
+        for _ in 0..5 {
+            assert_ok!(CoursesModule::create_learning_path(
+                Origin::signed(OWNER),
+                b"Rust track".to_vec(),
+                vec![course_id],
+                Percent::zero(),
+            ));
+        }
+        for path_id in 0..4 {
+            assert_ok!(CoursesModule::enroll_in_path(Origin::signed(LEARNER), path_id));
+        }
+        assert_noop!(
+            CoursesModule::enroll_in_path(Origin::signed(LEARNER), 4),
+            Error::<Test>::TooManyPaths
+        );
+    });
+}
+
+#[test]
+fn learning_paths_are_validated() {
+    new_test_ext().execute_with(|| {
+        let (course_id, _) = published_course(OWNER, 1, None);
+        let (foreign, _) = published_course(LEARNER, 1, None);
+
+        assert_noop!(
+            CoursesModule::create_learning_path(Origin::signed(OWNER), b"Empty".to_vec(), vec![], Percent::zero()),
+            Error::<Test>::InvalidPathCourses
+        );
+        assert_noop!(
+            CoursesModule::create_learning_path(
+                Origin::signed(OWNER),
+                b"Too long".to_vec(),
+                vec![course_id; 9],
+                Percent::zero(),
+            ),
+            Error::<Test>::InvalidPathCourses
+        );
+        assert_noop!(
+            CoursesModule::create_learning_path(
+                Origin::signed(OWNER),
+                b"Twice".to_vec(),
+                vec![course_id, course_id],
+                Percent::zero(),
+            ),
+            Error::<Test>::DuplicatePathCourse
+        );
+        assert_noop!(
+            CoursesModule::create_learning_path(
+                Origin::signed(OWNER),
+                b"Foreign".to_vec(),
+                vec![course_id, foreign],
+                Percent::zero(),
+            ),
+            Error::<Test>::NotCourseOwner
+        );
+    });
+}
+
+#[test]
+fn coupons_discount_the_enrollment_price() {
+    new_test_ext().execute_with(|| {
+        let (course_id, _) = priced_course(OWNER, 1, 100, PaymentCurrency::Native);
+        let percentage = H256::repeat_byte(1);
+        let fixed = H256::repeat_byte(2);
+        assert_ok!(CoursesModule::create_coupon(
+            Origin::signed(OWNER),
+            percentage,
+            Discount::Percentage(Percent::from_percent(20)),
+            2,
+            10,
+            Some(course_id),
+        ));
+        assert_ok!(CoursesModule::create_coupon(Origin::signed(OWNER), fixed, Discount::Fixed(30), 2, 10, None));
+
+        assert_ok!(CoursesModule::enroll(Origin::signed(LEARNER), course_id, Some(percentage), None));
+        assert_ok!(CoursesModule::enroll(Origin::signed(REPORTER), course_id, Some(fixed), None));
+
+        assert_eq!(Balances::free_balance(LEARNER), 1_000 - 80);
+        assert_eq!(Balances::free_balance(REPORTER), 1_000 - 70);
+        assert_eq!(Balances::free_balance(OWNER), 1_000 - 10 + 80 + 70);
+        assert_eq!(CoursesModule::coupons(percentage).unwrap().redemptions, 1);
+        assert!(CoursesModule::coupon_redemptions(percentage, LEARNER).is_some());
+        assert_eq!(CoursesModule::course_revenue(course_id, PaymentCurrency::Native), 150);
+    });
+}
+
+#[test]
+fn coupons_are_exhausted_and_redeemed_once_per_learner() {
+    new_test_ext().execute_with(|| {
+        let (course_id, _) = priced_course(OWNER, 1, 100, PaymentCurrency::Native);
+        let (other, _) = priced_course(OWNER, 1, 100, PaymentCurrency::Native);
+        let code = H256::repeat_byte(1);
+        assert_ok!(CoursesModule::create_coupon(
+            Origin::signed(OWNER),
+            code,
+            Discount::Fixed(10),
+            2,
+            10,
+            None,
+        ));
+
+        assert_ok!(CoursesModule::enroll(Origin::signed(LEARNER), course_id, Some(code), None));
+        assert_noop!(
+            CoursesModule::enroll(Origin::signed(LEARNER), other, Some(code), None),
+            Error::<Test>::CouponAlreadyRedeemed
+        );
+        assert_ok!(CoursesModule::enroll(Origin::signed(REPORTER), course_id, Some(code), None));
+        assert_noop!(
+            CoursesModule::enroll(Origin::signed(FUNDER), course_id, Some(code), None),
+            Error::<Test>::CouponExhausted
+        );
+    });
+}
+
+#[test]
+fn coupons_are_limited_in_time_and_scope() {
+    new_test_ext().execute_with(|| {
+        let (course_id, _) = priced_course(OWNER, 1, 100, PaymentCurrency::Native);
+        let (other, _) = priced_course(OWNER, 1, 100, PaymentCurrency::Native);
+        let (foreign, _) = priced_course(REPORTER, 1, 100, PaymentCurrency::Native);
+        let code = H256::repeat_byte(1);
+        assert_ok!(CoursesModule::create_coupon(
+            Origin::signed(OWNER),
+            code,
+            Discount::Fixed(10),
+            4,
+            5,
+            Some(course_id),
+        ));
+
+        assert_noop!(
+            CoursesModule::enroll(Origin::signed(LEARNER), other, Some(code), None),
+            Error::<Test>::CouponNotApplicable
+        );
+        assert_noop!(
+            CoursesModule::enroll(Origin::signed(LEARNER), foreign, Some(code), None),
+            Error::<Test>::CouponNotApplicable
+        );
+        System::set_block_number(6);
+        assert_noop!(
+            CoursesModule::enroll(Origin::signed(LEARNER), course_id, Some(code), None),
+            Error::<Test>::CouponExpired
+        );
+        assert_noop!(
+            CoursesModule::create_coupon(Origin::signed(OWNER), H256::repeat_byte(2), Discount::Fixed(10), 4, 6, None),
+            Error::<Test>::CouponExpired
+        );
+    });
+}
+
+#[test]
+fn coupon_redemptions_are_capped() {
+    new_test_ext().execute_with(|| {
+        let code = H256::repeat_byte(1);
+        assert_noop!(
+            CoursesModule::create_coupon(Origin::signed(OWNER), code, Discount::Fixed(10), 0, 10, None),
+            Error::<Test>::InvalidRedemptionLimit
+        );
+        assert_noop!(
+            CoursesModule::create_coupon(Origin::signed(OWNER), code, Discount::Fixed(10), 9, 10, None),
+            Error::<Test>::InvalidRedemptionLimit
+        );
+        assert_ok!(CoursesModule::create_coupon(Origin::signed(OWNER), code, Discount::Fixed(10), 8, 10, None));
+        assert_noop!(
+            CoursesModule::create_coupon(Origin::signed(OWNER), code, Discount::Fixed(10), 8, 10, None),
+            Error::<Test>::CouponAlreadyExists
+        );
+    });
+}
+
+#[test]
+fn revoked_coupons_can_no_longer_be_redeemed() {
+    new_test_ext().execute_with(|| {
+        let (course_id, _) = priced_course(OWNER, 1, 100, PaymentCurrency::Native);
+        let code = H256::repeat_byte(1);
+        assert_ok!(CoursesModule::create_coupon(Origin::signed(OWNER), code, Discount::Fixed(10), 4, 10, None));
+        assert_ok!(CoursesModule::enroll(Origin::signed(LEARNER), course_id, Some(code), None));
+
+        assert_noop!(
+            CoursesModule::revoke_coupon(Origin::signed(LEARNER), code),
+            Error::<Test>::NotCouponOwner
+        );
+        assert_ok!(CoursesModule::revoke_coupon(Origin::signed(OWNER), code));
+        assert!(CoursesModule::coupon_redemptions(code, LEARNER).is_none());
+        assert_noop!(
+            CoursesModule::enroll(Origin::signed(REPORTER), course_id, Some(code), None),
+            Error::<Test>::CouponNotExist
+        );
+    });
+}
+
+#[test]
+fn gifts_enroll_the_recipient_at_the_payer_expense() {
+    new_test_ext().execute_with(|| {
+        let (course_id, _) = priced_course(OWNER, 1, 100, PaymentCurrency::Native);
+        assert_noop!(
+            CoursesModule::gift_enrollment(Origin::signed(REPORTER), course_id, REPORTER, None, false),
+            Error::<Test>::GiftToSelf
+        );
+
+        assert_ok!(CoursesModule::gift_enrollment(Origin::signed(REPORTER), course_id, LEARNER, None, false));
+        assert_eq!(Balances::free_balance(REPORTER), 1_000 - 100);
+        assert_eq!(Balances::free_balance(LEARNER), 1_000);
+        assert_eq!(Balances::free_balance(OWNER), 1_000 - 10 + 100);
+        assert_eq!(CoursesModule::enrollments(LEARNER, course_id).unwrap().paid, 100);
+    });
+}
+
+#[test]
+fn claimable_gifts_are_escrowed_until_accepted() {
+    new_test_ext().execute_with(|| {
+        let (course_id, _) = priced_course(OWNER, 1, 100, PaymentCurrency::Native);
+        let escrow = CoursesModule::gift_escrow();
+
+        assert_ok!(CoursesModule::gift_enrollment(Origin::signed(REPORTER), course_id, LEARNER, None, true));
+        assert_eq!(Balances::free_balance(REPORTER), 1_000 - 100);
+        assert_eq!(Balances::free_balance(escrow), 100);
+        assert!(CoursesModule::enrollments(LEARNER, course_id).is_none());
+        assert_noop!(
+            CoursesModule::gift_enrollment(Origin::signed(FUNDER), course_id, LEARNER, None, true),
+            Error::<Test>::GiftAlreadyPending
+        );
+
+        assert_ok!(CoursesModule::accept_gift(Origin::signed(LEARNER), course_id));
+        assert_eq!(Balances::free_balance(escrow), 0);
+        assert_eq!(Balances::free_balance(OWNER), 1_000 - 10 + 100);
+        assert_eq!(CoursesModule::enrollments(LEARNER, course_id).unwrap().paid, 100);
+        assert!(CoursesModule::pending_gifts(LEARNER, course_id).is_none());
+    });
+}
+
+#[test]
+fn cancelled_gifts_refund_the_payer_and_restore_the_coupon() {
+    new_test_ext().execute_with(|| {
+        let (course_id, _) = priced_course(OWNER, 1, 100, PaymentCurrency::Native);
+        let code = H256::repeat_byte(1);
+        assert_ok!(CoursesModule::create_coupon(Origin::signed(OWNER), code, Discount::Fixed(10), 1, 10, None));
+        assert_ok!(CoursesModule::gift_enrollment(Origin::signed(REPORTER), course_id, LEARNER, Some(code), true));
+        assert_eq!(Balances::free_balance(REPORTER), 1_000 - 90);
+        assert_eq!(CoursesModule::coupons(code).unwrap().redemptions, 1);
+
+        assert_noop!(
+            CoursesModule::cancel_gift(Origin::signed(FUNDER), course_id, LEARNER),
+            Error::<Test>::NotGiftParty
+        );
+        assert_ok!(CoursesModule::cancel_gift(Origin::signed(LEARNER), course_id, LEARNER));
+
+        assert_eq!(Balances::free_balance(REPORTER), 1_000);
+        assert_eq!(Balances::free_balance(CoursesModule::gift_escrow()), 0);
+        assert_eq!(CoursesModule::coupons(code).unwrap().redemptions, 0);
+        assert!(CoursesModule::coupon_redemptions(code, REPORTER).is_none());
+        assert_noop!(
+            CoursesModule::accept_gift(Origin::signed(LEARNER), course_id),
+            Error::<Test>::GiftNotExist
+        );
+        // The payer gets the coupon back, and can redeem it again.
+        assert_ok!(CoursesModule::enroll(Origin::signed(REPORTER), course_id, Some(code), None));
+        assert_eq!(Balances::free_balance(REPORTER), 1_000 - 90);
+    });
+}
+
+#[test]
+fn enrollment_rules_check_prerequisites_and_capacity() {
+    new_test_ext().execute_with(|| {
+        let (basics, basics_lectures) = published_course(OWNER, 1, None);
+        let (course_id, _) = priced_course(OWNER, 1, 100, PaymentCurrency::Native);
+        assert_noop!(
+            CoursesModule::set_enrollment_rules(Origin::signed(OWNER), course_id, vec![course_id], None),
+            Error::<Test>::InvalidPrerequisites
+        );
+        assert_noop!(
+            CoursesModule::set_enrollment_rules(Origin::signed(OWNER), course_id, vec![basics; 5], None),
+            Error::<Test>::InvalidPrerequisites
+        );
+        assert_ok!(CoursesModule::set_enrollment_rules(Origin::signed(OWNER), course_id, vec![basics], Some(1)));
+
+        assert_noop!(
+            CoursesModule::enroll(Origin::signed(LEARNER), course_id, None, None),
+            Error::<Test>::PrerequisitesNotMet
+        );
+        complete_all(LEARNER, basics, &basics_lectures);
+        assert_ok!(CoursesModule::enroll(Origin::signed(LEARNER), course_id, None, None));
+
+        complete_all(REPORTER, basics, &basics_lectures);
+        assert_noop!(
+            CoursesModule::enroll(Origin::signed(REPORTER), course_id, None, None),
+            Error::<Test>::CourseFull
+        );
+        assert_noop!(
+            CoursesModule::gift_enrollment(Origin::signed(FUNDER), course_id, REPORTER, None, true),
+            Error::<Test>::CourseFull
+        );
+    });
+}
+
+#[test]
+fn courses_can_only_be_priced_in_existing_assets() {
+    new_test_ext().execute_with(|| {
+        let course_id = create_course(OWNER);
+        assert_noop!(
+            CoursesModule::set_price(Origin::signed(OWNER), course_id, Some(100), PaymentCurrency::Asset(7)),
+            Error::<Test>::AssetNotExist
+        );
+        create_asset(7, &[], 0);
+        assert_ok!(CoursesModule::set_price(Origin::signed(OWNER), course_id, Some(100), PaymentCurrency::Asset(7)));
+        assert_eq!(CoursesModule::courses(course_id).unwrap().currency, PaymentCurrency::Asset(7));
+    });
+}
+
+#[test]
+fn enrollments_are_paid_in_the_course_asset() {
+    new_test_ext().execute_with(|| {
+        create_asset(7, &[LEARNER], 500);
+        let (course_id, _) = priced_course(OWNER, 1, 100, PaymentCurrency::Asset(7));
+
+        assert_noop!(
+            CoursesModule::enroll(Origin::signed(REPORTER), course_id, None, None),
+            Error::<Test>::NotEnoughBalance
+        );
+        assert_ok!(CoursesModule::enroll(Origin::signed(LEARNER), course_id, None, None));
+
+        assert_eq!(Assets::balance(7, LEARNER), 400);
+        assert_eq!(Assets::balance(7, OWNER), 100);
+        assert_eq!(Balances::free_balance(LEARNER), 1_000);
+        assert_eq!(CoursesModule::course_revenue(course_id, PaymentCurrency::Asset(7)), 100);
+        assert_eq!(CoursesModule::course_revenue(course_id, PaymentCurrency::Native), 0);
+    });
+}
+
+#[test]
+fn gifts_settle_in_the_asset_they_were_paid_in() {
+    new_test_ext().execute_with(|| {
+        create_asset(7, &[REPORTER], 500);
+        let (course_id, _) = priced_course(OWNER, 1, 100, PaymentCurrency::Asset(7));
+        assert_ok!(CoursesModule::gift_enrollment(Origin::signed(REPORTER), course_id, LEARNER, None, true));
+        assert_eq!(Assets::balance(7, CoursesModule::gift_escrow()), 100);
+
+        // Repricing the course does not change what the escrow pays out.
+        assert_ok!(CoursesModule::set_price(Origin::signed(OWNER), course_id, Some(300), PaymentCurrency::Native));
+        assert_ok!(CoursesModule::accept_gift(Origin::signed(LEARNER), course_id));
+
+        assert_eq!(Assets::balance(7, CoursesModule::gift_escrow()), 0);
+        assert_eq!(Assets::balance(7, OWNER), 100);
+        assert_eq!(Balances::free_balance(OWNER), 1_000 - 10);
+    });
+}
+
+#[test]
+fn lectures_are_released_at_a_fixed_block() {
+    new_test_ext().execute_with(|| {
+        let (course_id, lectures) = published_course(OWNER, 2, None);
+        assert_ok!(CoursesModule::set_lecture_release(
+            Origin::signed(OWNER),
+            course_id,
+            lectures[1],
+            Some(ReleaseSchedule::At(5)),
+        ));
+
+        let released: Vec<_> = CoursesModule::released_lectures(&course_id, None).into_iter().map(|(id, _)| id).collect();
+        assert_eq!(released, vec![lectures[0]]);
+        assert_eq!(CoursesModule::released_lectures(&course_id, Some(OWNER)).len(), 2);
+        assert_noop!(
+            CoursesModule::complete_lecture(Origin::signed(LEARNER), course_id, lectures[1]),
+            Error::<Test>::LectureNotReleased
+        );
+
+        System::set_block_number(5);
+        assert_eq!(CoursesModule::released_lectures(&course_id, None).len(), 2);
+        assert_ok!(CoursesModule::complete_lecture(Origin::signed(LEARNER), course_id, lectures[1]));
+    });
+}
+
+#[test]
+fn lectures_are_released_relative_to_the_enrollment() {
+    new_test_ext().execute_with(|| {
+        let (course_id, lectures) = priced_course(OWNER, 1, 100, PaymentCurrency::Native);
+        assert_ok!(CoursesModule::set_lecture_release(
+            Origin::signed(OWNER),
+            course_id,
+            lectures[0],
+            Some(ReleaseSchedule::AfterEnrollment(3)),
+        ));
+        System::set_block_number(2);
+        assert_ok!(CoursesModule::enroll(Origin::signed(LEARNER), course_id, None, None));
+
+        System::set_block_number(4);
+        assert_noop!(
+            CoursesModule::complete_lecture(Origin::signed(LEARNER), course_id, lectures[0]),
+            Error::<Test>::LectureNotReleased
+        );
+        assert!(CoursesModule::released_lectures(&course_id, Some(LEARNER)).is_empty());
+
+        System::set_block_number(5);
+        assert_eq!(CoursesModule::released_lectures(&course_id, Some(LEARNER)).len(), 1);
+        // Accounts not enrolled never see lectures released relative to an enrollment.
+        assert!(CoursesModule::released_lectures(&course_id, Some(REPORTER)).is_empty());
+        assert_ok!(CoursesModule::complete_lecture(Origin::signed(LEARNER), course_id, lectures[0]));
+    });
+}
+
+#[test]
+fn learners_keep_studying_the_edition_they_started() {
+    new_test_ext().execute_with(|| {
+        let (course_id, lectures) = published_course(OWNER, 2, None);
+        complete_all(LEARNER, course_id, &lectures[..1]);
+
+        let added = create_lecture(OWNER, course_id);
+        assert_noop!(
+            CoursesModule::complete_lecture(Origin::signed(LEARNER), course_id, added),
+            Error::<Test>::LectureNotInEdition
+        );
+        assert_ok!(CoursesModule::publish_edition(Origin::signed(OWNER), course_id));
+        assert_eq!(CoursesModule::current_edition(course_id), 2);
+
+        assert_noop!(
+            CoursesModule::complete_lecture(Origin::signed(LEARNER), course_id, added),
+            Error::<Test>::LectureNotInEdition
+        );
+        complete_all(LEARNER, course_id, &lectures[1..]);
+        assert_eq!(CoursesModule::courses_completed(LEARNER, course_id).unwrap().edition, 1);
+
+        // New learners study the current edition.
+        assert_ok!(CoursesModule::complete_lecture(Origin::signed(REPORTER), course_id, added));
+        assert_eq!(CoursesModule::learner_editions(REPORTER, course_id), Some(2));
+    });
+}
+
+#[test]
+fn migrating_progress_drops_changed_lectures() {
+    new_test_ext().execute_with(|| {
+        let (course_id, lectures) = published_course(OWNER, 2, None);
+        assert_noop!(
+            CoursesModule::migrate_progress(Origin::signed(LEARNER), course_id),
+            Error::<Test>::CourseNotStarted
+        );
+        complete_all(LEARNER, course_id, &lectures);
+        assert_noop!(
+            CoursesModule::migrate_progress(Origin::signed(LEARNER), course_id),
+            Error::<Test>::AlreadyOnCurrentEdition
+        );
+
+        assert_ok!(CoursesModule::update_lecture(
+            Origin::signed(OWNER),
+            course_id,
+            lectures[0],
+            b"Lecture".to_vec(),
+            b"Revised".to_vec(),
+        ));
+        assert_ok!(CoursesModule::publish_edition(Origin::signed(OWNER), course_id));
+        assert_ok!(CoursesModule::migrate_progress(Origin::signed(LEARNER), course_id));
+
+        assert!(CoursesModule::lectures_completed((LEARNER, course_id, lectures[0])).is_none());
+        assert_eq!(CoursesModule::lectures_completed((LEARNER, course_id, lectures[1])).unwrap().edition, 2);
+        assert_eq!(CoursesModule::lecture_completion_count(course_id, lectures[0]), 0);
+        assert_eq!(CoursesModule::course_lecture_count(LEARNER, course_id), 1);
+        assert_eq!(CoursesModule::learner_editions(LEARNER, course_id), Some(2));
+
+        // Completing the revised lecture completes the new edition, counted once.
+        assert_ok!(CoursesModule::complete_lecture(Origin::signed(LEARNER), course_id, lectures[0]));
+        assert_eq!(CoursesModule::courses_completed(LEARNER, course_id).unwrap().edition, 2);
+        assert_eq!(CoursesModule::completion_count(course_id), 1);
+    });
+}
+
+#[test]
+fn migrating_progress_completes_the_course_when_every_lecture_is_kept() {
+    new_test_ext().execute_with(|| {
+        let (course_id, lectures) = published_course(OWNER, 3, None);
+        complete_all(LEARNER, course_id, &lectures[..2]);
+        assert!(CoursesModule::courses_completed(LEARNER, course_id).is_none());
+
+        assert_ok!(CoursesModule::remove_lecture(Origin::signed(OWNER), course_id, lectures[2]));
+        assert_ok!(CoursesModule::publish_edition(Origin::signed(OWNER), course_id));
+        assert_ok!(CoursesModule::migrate_progress(Origin::signed(LEARNER), course_id));
+
+        let completion = CoursesModule::courses_completed(LEARNER, course_id).unwrap();
+        assert_eq!(completion.edition, 2);
+        assert_eq!(CoursesModule::completion_count(course_id), 1);
+        assert_eq!(CoursesModule::course_lecture_count(LEARNER, course_id), 2);
+    });
+}
+
+#[test]
+fn referrers_earn_a_share_of_the_sale() {
+    new_test_ext().execute_with(|| {
+        let (course_id, _) = priced_course(OWNER, 1, 100, PaymentCurrency::Native);
+        assert_ok!(CoursesModule::set_referral_commission(Origin::signed(OWNER), course_id, Perbill::from_percent(10)));
+
+        assert_ok!(CoursesModule::enroll(Origin::signed(LEARNER), course_id, None, Some(REPORTER)));
+
+        assert_eq!(Balances::free_balance(LEARNER), 1_000 - 100);
+        assert_eq!(Balances::free_balance(REPORTER), 1_000 + 10);
+        assert_eq!(Balances::free_balance(OWNER), 1_000 - 10 + 90);
+        let stats = CoursesModule::referral_stats(REPORTER, PaymentCurrency::Native);
+        assert_eq!(stats.referrals, 1);
+        assert_eq!(stats.earned, 10);
+        // Revenue is recorded gross of the commission.
+        assert_eq!(CoursesModule::course_revenue(course_id, PaymentCurrency::Native), 100);
+    });
+}
+
+#[test]
+fn referral_commission_applies_to_the_discounted_price() {
+    new_test_ext().execute_with(|| {
+        create_asset(7, &[LEARNER], 500);
+        let (course_id, _) = priced_course(OWNER, 1, 100, PaymentCurrency::Asset(7));
+        assert_ok!(CoursesModule::set_referral_commission(Origin::signed(OWNER), course_id, Perbill::from_percent(10)));
+        let code = H256::repeat_byte(1);
+        assert_ok!(CoursesModule::create_coupon(Origin::signed(OWNER), code, Discount::Fixed(50), 1, 10, None));
+
+        assert_ok!(CoursesModule::enroll(Origin::signed(LEARNER), course_id, Some(code), Some(REPORTER)));
+
+        assert_eq!(Assets::balance(7, REPORTER), 5);
+        assert_eq!(Assets::balance(7, OWNER), 45);
+        assert_eq!(CoursesModule::referral_stats(REPORTER, PaymentCurrency::Asset(7)).earned, 5);
+        assert_eq!(CoursesModule::referral_stats(REPORTER, PaymentCurrency::Native).referrals, 0);
+    });
+}
+
+#[test]
+fn learners_and_owners_cannot_refer_themselves() {
+    new_test_ext().execute_with(|| {
+        let (course_id, _) = priced_course(OWNER, 1, 100, PaymentCurrency::Native);
+        assert_ok!(CoursesModule::set_referral_commission(Origin::signed(OWNER), course_id, Perbill::from_percent(10)));
+
+        assert_noop!(
+            CoursesModule::enroll(Origin::signed(LEARNER), course_id, None, Some(LEARNER)),
+            Error::<Test>::InvalidReferrer
+        );
+        assert_noop!(
+            CoursesModule::enroll(Origin::signed(LEARNER), course_id, None, Some(OWNER)),
+            Error::<Test>::InvalidReferrer
+        );
+        assert_noop!(
+            CoursesModule::set_referral_commission(Origin::signed(LEARNER), course_id, Perbill::from_percent(50)),
+            Error::<Test>::NotCourseOwner
+        );
+    });
+}
+
+#[test]
+fn identical_courses_and_lectures_get_distinct_ids() {
+    new_test_ext().execute_with(|| {
+        let first = create_course(OWNER);
+        let second = create_course(OWNER);
+        assert_ne!(first, second);
+        assert_eq!(CoursesModule::course_cnt(), 2);
+        assert_eq!(CoursesModule::courses_owned(OWNER).into_inner(), vec![first, second]);
+
+        for _ in 0..2 {
+            assert_ok!(CoursesModule::create_lecture(
+                Origin::signed(OWNER),
+                first,
+                b"Lecture".to_vec(),
+                b"Contents".to_vec(),
+            ));
+        }
+        assert_eq!(CoursesModule::released_lectures(&first, None).len(), 2);
+
+        // Ids do not depend on the contents, and removed ids are never handed out again.
+        let lecture_id = create_lecture(OWNER, first);
+        assert_ok!(CoursesModule::remove_lecture(Origin::signed(OWNER), first, lecture_id));
+        let recreated = create_lecture(OWNER, first);
+        assert_ne!(lecture_id, recreated);
+        assert!(CoursesModule::lectures(first, recreated).is_some());
+    });
+}
+
+#[test]
+fn genesis_seeds_courses_enrollments_and_classes() {
+    let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+    pallet_balances::GenesisConfig::<Test> { balances: vec![(OWNER, 1_000)] }
+        .assimilate_storage(&mut t)
+        .unwrap();
+    let lecture = |name: &[u8]| GenesisLecture {
+        name: name.to_vec(),
+        contents: b"Contents".to_vec(),
+        kind: LectureKind::Standard,
+    };
+    GenesisConfig::<Test> {
+        courses: vec![GenesisCourse {
+            owner: OWNER,
+            name: b"Rust".to_vec(),
+            category: b"programming".to_vec(),
+            image_url: b"ipfs://image".to_vec(),
+            description: b"Learn Rust".to_vec(),
+            price: Some(100),
+            lectures: vec![lecture(b"Ownership"), lecture(b"Borrowing")],
+            live: true,
+            enrollments: vec![LEARNER],
+        }],
+        classes: vec![GenesisClass {
+            owner: OWNER,
+            token_type: TokenType::Transferable,
+            tokens: vec![LEARNER, REPORTER],
+        }],
+    }
+    .assimilate_storage(&mut t)
+    .unwrap();
+
+    sp_io::TestExternalities::new(t).execute_with(|| {
+        System::set_block_number(1);
+        let course_id = CoursesModule::courses_owned(OWNER)[0];
+        let course = CoursesModule::courses(course_id).unwrap();
+        assert!(course.live);
+        assert_eq!(course.price, Some(100));
+        assert_eq!(Balances::reserved_balance(OWNER), 10);
+        assert_eq!(CoursesModule::current_edition(course_id), 1);
+        assert_eq!(CoursesModule::enrollments(LEARNER, course_id).unwrap().paid, 0);
+        assert_eq!(CoursesModule::enrollment_count(course_id), 1);
+        assert_eq!(CoursesModule::tokens(0, 0).unwrap().owner, LEARNER);
+        assert_eq!(CoursesModule::tokens(0, 1).unwrap().owner, REPORTER);
+
+        let lectures: Vec<_> = CoursesModule::released_lectures(&course_id, None).into_iter().map(|(id, _)| id).collect();
+        assert_eq!(lectures.len(), 2);
+        complete_all(LEARNER, course_id, &lectures);
+        assert!(CoursesModule::courses_completed(LEARNER, course_id).is_some());
+    });
+}
+
+#[test]
+fn encrypted_lectures_require_a_granted_key() {
+    new_test_ext().execute_with(|| {
+        let course_id = create_course(OWNER);
+        let plain = create_lecture(OWNER, course_id);
+        let encrypted = create_encrypted_lecture(OWNER, course_id);
+        assert_ok!(CoursesModule::set_price(Origin::signed(OWNER), course_id, Some(100), PaymentCurrency::Native));
+        assert_ok!(CoursesModule::publish_course(Origin::signed(OWNER), course_id));
+
+        assert_noop!(
+            CoursesModule::grant_lecture_key(Origin::signed(OWNER), course_id, encrypted, LEARNER, vec![1; 32]),
+            Error::<Test>::NotEnrolled
+        );
+        assert_ok!(CoursesModule::enroll(Origin::signed(LEARNER), course_id, None, None));
+        assert_noop!(
+            CoursesModule::complete_lecture(Origin::signed(LEARNER), course_id, encrypted),
+            Error::<Test>::LectureKeyNotGranted
+        );
+        assert_noop!(
+            CoursesModule::grant_lecture_key(Origin::signed(OWNER), course_id, encrypted, LEARNER, vec![1; 32]),
+            Error::<Test>::EncryptionKeyNotRegistered
+        );
+
+        assert_ok!(CoursesModule::register_encryption_key(Origin::signed(LEARNER), [7; 32]));
+        assert_noop!(
+            CoursesModule::grant_lecture_key(Origin::signed(OWNER), course_id, plain, LEARNER, vec![1; 32]),
+            Error::<Test>::LectureNotEncrypted
+        );
+        assert_noop!(
+            CoursesModule::grant_lecture_key(Origin::signed(OWNER), course_id, encrypted, LEARNER, vec![1; 65]),
+            Error::<Test>::WrappedKeyTooLong
+        );
+        assert_noop!(
+            CoursesModule::grant_lecture_key(Origin::signed(LEARNER), course_id, encrypted, LEARNER, vec![1; 32]),
+            Error::<Test>::NotCourseOwner
+        );
+        assert_ok!(CoursesModule::grant_lecture_key(Origin::signed(OWNER), course_id, encrypted, LEARNER, vec![1; 32]));
+
+        assert_eq!(CoursesModule::wrapped_keys((course_id, encrypted, LEARNER)).unwrap().key.into_inner(), vec![1; 32]);
+        assert_ok!(CoursesModule::complete_lecture(Origin::signed(LEARNER), course_id, encrypted));
+    });
+}
+
+#[test]
+fn publishing_can_require_a_verified_instructor() {
+    new_test_ext().execute_with(|| {
+        let course_id = create_course(OWNER);
+        RequireVerifiedInstructor::set(true);
+        assert!(!CoursesModule::is_verified_instructor(&OWNER));
+        assert_noop!(
+            CoursesModule::publish_course(Origin::signed(OWNER), course_id),
+            Error::<Test>::InstructorNotVerified
+        );
+
+        VerifiedInstructors::set(vec![OWNER]);
+        assert!(CoursesModule::is_verified_instructor(&OWNER));
+        assert_ok!(CoursesModule::publish_course(Origin::signed(OWNER), course_id));
+    });
+}
+
+#[test]
+fn analytics_count_enrollments_completions_and_revenue() {
+    new_test_ext().execute_with(|| {
+        create_asset(7, &[REPORTER], 500);
+        let (course_id, lectures) = priced_course(OWNER, 2, 100, PaymentCurrency::Native);
+        assert_ok!(CoursesModule::enroll(Origin::signed(LEARNER), course_id, None, None));
+        assert_ok!(CoursesModule::set_price(Origin::signed(OWNER), course_id, Some(40), PaymentCurrency::Asset(7)));
+        assert_ok!(CoursesModule::enroll(Origin::signed(REPORTER), course_id, None, None));
+        complete_all(LEARNER, course_id, &lectures);
+        complete_all(REPORTER, course_id, &lectures[..1]);
+
+        let analytics = CoursesModule::course_analytics(&course_id);
+        assert_eq!(analytics.enrollments, 2);
+        assert_eq!(analytics.completions, 1);
+        let mut lecture_completions = analytics.lecture_completions;
+        lecture_completions.sort();
+        let mut expected = vec![(lectures[0], 2), (lectures[1], 1)];
+        expected.sort();
+        assert_eq!(lecture_completions, expected);
+        let mut revenue = analytics.revenue;
+        revenue.sort_by_key(|(currency, _)| *currency != PaymentCurrency::Native);
+        assert_eq!(revenue, vec![(PaymentCurrency::Native, 100), (PaymentCurrency::Asset(7), 40)]);
+    });
+}
+
+#[test]
+fn courses_are_created_with_their_sections() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(CoursesModule::create_course_with_lectures(
+            Origin::signed(OWNER),
+            b"Rust".to_vec(),
+            b"programming".to_vec(),
+            b"ipfs://image".to_vec(),
+            b"Learn Rust".to_vec(),
+            vec![section(b"Basics", 2), section(b"Advanced", 1)],
+        ));
+        let course_id = CoursesModule::courses_owned(OWNER)[0];
+
+        assert_eq!(CoursesModule::next_section_id(course_id), 2);
+        let basics = CoursesModule::sections(course_id, 0).unwrap();
+        assert_eq!(basics.name, b"Basics".to_vec());
+        assert_eq!(basics.lectures.len(), 2);
+        assert_eq!(CoursesModule::sections(course_id, 1).unwrap().lectures.len(), 1);
+        assert_eq!(CoursesModule::released_lectures(&course_id, None).len(), 3);
+        assert_eq!(CoursesModule::lecture_sections(course_id, basics.lectures[1]), Some(0));
+
+        // Removed lectures leave their section.
+        assert_ok!(CoursesModule::remove_lecture(Origin::signed(OWNER), course_id, basics.lectures[0]));
+        assert_eq!(CoursesModule::sections(course_id, 0).unwrap().lectures.into_inner(), vec![basics.lectures[1]]);
+        assert!(CoursesModule::lecture_sections(course_id, basics.lectures[0]).is_none());
+    });
+}
+
+#[test]
+fn bulk_imports_are_bounded_and_atomic() {
+    new_test_ext().execute_with(|| {
+        let create = |sections: Vec<SectionInput>| {
+            CoursesModule::create_course_with_lectures(
+                Origin::signed(OWNER),
+                b"Rust".to_vec(),
+                b"programming".to_vec(),
+                b"ipfs://image".to_vec(),
+                b"Learn Rust".to_vec(),
+                sections,
+            )
+        };
+        assert_noop!(create(vec![section(b"Basics", 10), section(b"Advanced", 7)]), Error::<Test>::TooManyLectures);
+        assert_noop!(create((0..17).map(|_| section(b"Empty", 0)).collect()), Error::<Test>::TooManySections);
+
+        let mut invalid = section(b"Basics", 2);
+        invalid.lectures[1].kind = LectureKind::Assignment { reviewers: 4 };
+        assert_noop!(create(vec![invalid]), Error::<Test>::InvalidReviewerCount);
+        assert_eq!(CoursesModule::course_cnt(), 0);
+
+        let course_id = create_course(OWNER);
+        assert_noop!(
+            CoursesModule::import_lectures(Origin::signed(LEARNER), course_id, vec![section(b"Basics", 1)]),
+            Error::<Test>::NotCourseOwner
+        );
+        assert_ok!(CoursesModule::import_lectures(Origin::signed(OWNER), course_id, vec![section(b"Basics", 16)]));
+        assert_eq!(CoursesModule::released_lectures(&course_id, None).len(), 16);
+    });
+}
+
+#[test]
+fn translations_fall_back_to_the_primary_language() {
+    new_test_ext().execute_with(|| {
+        let (course_id, lectures) = published_course(OWNER, 1, None);
+        assert_noop!(
+            CoursesModule::set_course_translation(Origin::signed(REPORTER), course_id, b"pt".to_vec(), vec![], vec![]),
+            Error::<Test>::NotTranslator
+        );
+        assert_ok!(CoursesModule::add_translator(Origin::signed(OWNER), course_id, REPORTER));
+        assert_ok!(CoursesModule::set_course_translation(
+            Origin::signed(REPORTER),
+            course_id,
+            b"pt".to_vec(),
+            b"Ferrugem".to_vec(),
+            b"Aprenda Rust".to_vec(),
+        ));
+        assert_ok!(CoursesModule::set_lecture_translation(
+            Origin::signed(REPORTER),
+            course_id,
+            lectures[0],
+            b"pt-BR".to_vec(),
+            b"Aula".to_vec(),
+            b"Conteudo".to_vec(),
+        ));
+
+        let course = CoursesModule::localized_course(&course_id, b"pt-BR".to_vec()).unwrap();
+        assert_eq!(course.language, Some(b"pt".to_vec()));
+        assert_eq!(course.name, b"Ferrugem".to_vec());
+        let course = CoursesModule::localized_course(&course_id, b"fr".to_vec()).unwrap();
+        assert_eq!(course.language, None);
+        assert_eq!(course.name, b"Rust".to_vec());
+
+        let localized = CoursesModule::localized_lectures(&course_id, None, b"PT-br".to_vec());
+        assert_eq!(localized[0].1.language, Some(b"pt-br".to_vec()));
+        assert_eq!(localized[0].1.body, b"Conteudo".to_vec());
+        let localized = CoursesModule::localized_lectures(&course_id, None, b"pt".to_vec());
+        assert_eq!(localized[0].1.language, None);
+    });
+}
+
+#[test]
+fn translations_are_capped_per_course_and_lecture() {
+    new_test_ext().execute_with(|| {
+        let (course_id, lectures) = published_course(OWNER, 1, None);
+        let languages: Vec<Vec<u8>> = vec![b"de".to_vec(), b"es".to_vec(), b"fr".to_vec(), b"it".to_vec()];
+        for language in languages.iter() {
+            assert_ok!(CoursesModule::set_course_translation(Origin::signed(OWNER), course_id, language.clone(), vec![], vec![]));
+            assert_ok!(CoursesModule::set_lecture_translation(
+                Origin::signed(OWNER),
+                course_id,
+                lectures[0],
+                language.clone(),
+                vec![],
+                vec![],
+            ));
+        }
+        assert_noop!(
+            CoursesModule::set_course_translation(Origin::signed(OWNER), course_id, b"pt".to_vec(), vec![], vec![]),
+            Error::<Test>::TooManyTranslations
+        );
+        assert_noop!(
+            CoursesModule::set_lecture_translation(Origin::signed(OWNER), course_id, lectures[0], b"pt".to_vec(), vec![], vec![]),
+            Error::<Test>::TooManyTranslations
+        );
+
+        // Updating a language does not take a new slot, removing one frees it.
+        assert_ok!(CoursesModule::set_course_translation(Origin::signed(OWNER), course_id, b"DE".to_vec(), vec![1], vec![]));
+        assert_ok!(CoursesModule::remove_translation(Origin::signed(OWNER), course_id, None, b"de".to_vec()));
+        assert_eq!(CoursesModule::translation_count(course_id, None::<H256>), 3);
+        assert_ok!(CoursesModule::set_course_translation(Origin::signed(OWNER), course_id, b"pt".to_vec(), vec![], vec![]));
+
+        assert_ok!(CoursesModule::remove_lecture(Origin::signed(OWNER), course_id, lectures[0]));
+        assert_eq!(CoursesModule::translation_count(course_id, Some(lectures[0])), 0);
+    });
+}
+
+#[test]
+fn encrypted_lectures_cannot_be_translated() {
+    new_test_ext().execute_with(|| {
+        let course_id = create_course(OWNER);
+        let encrypted = create_encrypted_lecture(OWNER, course_id);
+        assert_noop!(
+            CoursesModule::set_lecture_translation(Origin::signed(OWNER), course_id, encrypted, b"pt".to_vec(), vec![], vec![]),
+            Error::<Test>::LectureEncrypted
+        );
+        assert_noop!(
+            CoursesModule::set_course_translation(Origin::signed(OWNER), course_id, vec![b'a'; 17], vec![], vec![]),
+            Error::<Test>::LanguageTagTooLong
+        );
+    });
+}
+
+fn fork(forker: u64, parent_id: H256) -> H256 {
+    assert_ok!(CoursesModule::fork_course(Origin::signed(forker), parent_id));
+    *CoursesModule::courses_owned(forker).last().expect("fork was just created")
+}
+
+#[test]
+fn licenses_decide_who_can_fork() {
+    new_test_ext().execute_with(|| {
+        let (parent_id, _) = published_course(OWNER, 1, None);
+        assert_noop!(
+            CoursesModule::fork_course(Origin::signed(REPORTER), parent_id),
+            Error::<Test>::LicenseForbidsForking
+        );
+        fork(OWNER, parent_id);
+
+        assert_ok!(CoursesModule::set_license(Origin::signed(OWNER), parent_id, License::CcBySa));
+        let fork_id = fork(REPORTER, parent_id);
+        let derivative = CoursesModule::courses(fork_id).unwrap();
+        assert_eq!(derivative.parent, Some(parent_id));
+        assert_eq!(derivative.license, License::CcBySa);
+        assert!(!derivative.live);
+        assert_noop!(
+            CoursesModule::set_license(Origin::signed(REPORTER), fork_id, License::CcBy),
+            Error::<Test>::ShareAlikeRequired
+        );
+    });
+}
+
+#[test]
+fn forks_copy_lectures_but_not_encrypted_ones() {
+    new_test_ext().execute_with(|| {
+        let parent_id = create_course(OWNER);
+        create_lecture(OWNER, parent_id);
+        create_lecture(OWNER, parent_id);
+        create_encrypted_lecture(OWNER, parent_id);
+        assert_ok!(CoursesModule::set_license(Origin::signed(OWNER), parent_id, License::CcBy));
+
+        let fork_id = fork(REPORTER, parent_id);
+        let lectures = CoursesModule::released_lectures(&fork_id, None);
+        assert_eq!(lectures.len(), 2);
+        assert!(lectures.iter().all(|(_, lecture)| lecture.owner == REPORTER));
+        assert_eq!(CoursesModule::courses(fork_id).unwrap().license, License::AllRightsReserved);
+    });
+}
+
+#[test]
+fn derivative_sales_pay_the_royalty_fixed_at_fork_time() {
+    new_test_ext().execute_with(|| {
+        let (parent_id, _) = published_course(OWNER, 1, None);
+        assert_ok!(CoursesModule::set_license(
+            Origin::signed(OWNER),
+            parent_id,
+            License::CommercialWithRoyalty(Perbill::from_percent(20)),
+        ));
+        let fork_id = fork(REPORTER, parent_id);
+        assert_eq!(CoursesModule::courses(fork_id).unwrap().parent_royalty, Some(Perbill::from_percent(20)));
+        assert_ok!(CoursesModule::set_price(Origin::signed(REPORTER), fork_id, Some(100), PaymentCurrency::Native));
+        assert_ok!(CoursesModule::publish_course(Origin::signed(REPORTER), fork_id));
+
+        // Relicensing the parent does not change the terms of existing forks.
+        assert_ok!(CoursesModule::set_license(Origin::signed(OWNER), parent_id, License::CcBy));
+        assert_ok!(CoursesModule::enroll(Origin::signed(LEARNER), fork_id, None, None));
+
+        assert_eq!(Balances::free_balance(LEARNER), 1_000 - 100);
+        assert_eq!(Balances::free_balance(OWNER), 1_000 - 10 + 20);
+        assert_eq!(Balances::free_balance(REPORTER), 1_000 - 10 + 80);
+        assert_eq!(CoursesModule::course_revenue(fork_id, PaymentCurrency::Native), 100);
+    });
+}
+
+#[test]
+fn derivative_royalties_come_after_the_referral_commission() {
+    new_test_ext().execute_with(|| {
+        let (parent_id, _) = published_course(OWNER, 1, None);
+        assert_ok!(CoursesModule::set_license(
+            Origin::signed(OWNER),
+            parent_id,
+            License::CommercialWithRoyalty(Perbill::from_percent(20)),
+        ));
+        let fork_id = fork(REPORTER, parent_id);
+        assert_ok!(CoursesModule::set_price(Origin::signed(REPORTER), fork_id, Some(100), PaymentCurrency::Native));
+        assert_ok!(CoursesModule::set_referral_commission(Origin::signed(REPORTER), fork_id, Perbill::from_percent(90)));
+        assert_ok!(CoursesModule::publish_course(Origin::signed(REPORTER), fork_id));
+
+        assert_ok!(CoursesModule::enroll(Origin::signed(LEARNER), fork_id, None, Some(FUNDER)));
+
+        // The royalty is bounded by what is left once the referrer is paid.
+        assert_eq!(Balances::free_balance(FUNDER), 1_000 + 90);
+        assert_eq!(Balances::free_balance(OWNER), 1_000 - 10 + 10);
+        assert_eq!(Balances::free_balance(REPORTER), 1_000 - 10);
+    });
+}
+
+// A transferable class owned by `OWNER`, with a token minted to `holder`.
+fn transferable_token(holder: u64) -> (u32, u64) {
+    let class_id = CoursesModule::create_class(&OWNER, TokenType::Transferable).unwrap();
+    let token_id = CoursesModule::mint_token(class_id, &holder).unwrap();
+    (class_id, token_id)
+}
+
+fn royalty(percent: u32) -> Option<ClassRoyalty<u64>> {
+    Some(ClassRoyalty { rate: Perbill::from_percent(percent), beneficiary: OWNER })
+}
+
+#[test]
+fn class_royalties_are_capped_and_set_by_the_owner() {
+    new_test_ext().execute_with(|| {
+        let (class_id, _) = transferable_token(LEARNER);
+        assert_noop!(
+            CoursesModule::set_class_royalty(Origin::signed(LEARNER), class_id, royalty(10)),
+            Error::<Test>::NotClassOwner
+        );
+        assert_noop!(
+            CoursesModule::set_class_royalty(Origin::signed(OWNER), class_id, royalty(26)),
+            Error::<Test>::RoyaltyTooHigh
+        );
+        assert_ok!(CoursesModule::set_class_royalty(Origin::signed(OWNER), class_id, royalty(25)));
+        assert_eq!(CoursesModule::class_royalties(class_id), royalty(25));
+        assert_ok!(CoursesModule::set_class_royalty(Origin::signed(OWNER), class_id, None));
+        assert!(CoursesModule::class_royalties(class_id).is_none());
+    });
+}
+
+#[test]
+fn token_sales_pay_the_royalty_in_force_at_listing() {
+    new_test_ext().execute_with(|| {
+        let (class_id, token_id) = transferable_token(LEARNER);
+        assert_ok!(CoursesModule::set_class_royalty(Origin::signed(OWNER), class_id, royalty(10)));
+        assert_ok!(CoursesModule::list_token(Origin::signed(LEARNER), class_id, token_id, 100));
+        assert_ok!(CoursesModule::set_class_royalty(Origin::signed(OWNER), class_id, royalty(25)));
+
+        assert_ok!(CoursesModule::buy_token(Origin::signed(REPORTER), class_id, token_id, 100));
+
+        assert_eq!(Balances::free_balance(REPORTER), 1_000 - 100);
+        assert_eq!(Balances::free_balance(OWNER), 1_000 + 10);
+        assert_eq!(Balances::free_balance(LEARNER), 1_000 + 90);
+        assert_eq!(CoursesModule::tokens(class_id, token_id).unwrap().owner, REPORTER);
+
+        // Relisting picks the new rate up.
+        assert_ok!(CoursesModule::list_token(Origin::signed(REPORTER), class_id, token_id, 100));
+        assert_eq!(CoursesModule::listings(class_id, token_id).unwrap().royalty, royalty(25));
+    });
+}
+
+#[test]
+fn royalty_beneficiaries_selling_their_tokens_keep_the_full_price() {
+    new_test_ext().execute_with(|| {
+        let (class_id, token_id) = transferable_token(OWNER);
+        assert_ok!(CoursesModule::set_class_royalty(Origin::signed(OWNER), class_id, royalty(10)));
+        assert_ok!(CoursesModule::list_token(Origin::signed(OWNER), class_id, token_id, 100));
+
+        assert_ok!(CoursesModule::buy_token(Origin::signed(REPORTER), class_id, token_id, 100));
+        assert_eq!(Balances::free_balance(OWNER), 1_000 + 100);
+    });
+}
+
+#[test]
+fn listed_tokens_are_sold_at_the_listing_price() {
+    new_test_ext().execute_with(|| {
+        let (class_id, token_id) = transferable_token(LEARNER);
+        assert_noop!(
+            CoursesModule::list_token(Origin::signed(REPORTER), class_id, token_id, 100),
+            Error::<Test>::NotTokenOwner
+        );
+        assert_noop!(
+            CoursesModule::buy_token(Origin::signed(REPORTER), class_id, token_id, 100),
+            Error::<Test>::TokenNotForSale
+        );
+        assert_ok!(CoursesModule::list_token(Origin::signed(LEARNER), class_id, token_id, 100));
+
+        assert_noop!(
+            CoursesModule::buy_token(Origin::signed(LEARNER), class_id, token_id, 100),
+            Error::<Test>::BuyerIsTokenOwner
+        );
+        assert_noop!(
+            CoursesModule::buy_token(Origin::signed(REPORTER), class_id, token_id, 99),
+            Error::<Test>::TokenBidPriceTooLow
+        );
+        assert_ok!(CoursesModule::list_token(Origin::signed(LEARNER), class_id, token_id, 2_000));
+        assert_noop!(
+            CoursesModule::buy_token(Origin::signed(REPORTER), class_id, token_id, 2_000),
+            Error::<Test>::NotEnoughBalance
+        );
+
+        assert_ok!(CoursesModule::list_token(Origin::signed(LEARNER), class_id, token_id, 100));
+        // Bidding above the price pays the listing price only.
+        assert_ok!(CoursesModule::buy_token(Origin::signed(REPORTER), class_id, token_id, 150));
+        assert_eq!(Balances::free_balance(REPORTER), 1_000 - 100);
+        assert_eq!(Balances::free_balance(LEARNER), 1_000 + 100);
+        assert!(CoursesModule::listings(class_id, token_id).is_none());
+        assert!(TokensByOwner::<Test>::contains_key((REPORTER, class_id, token_id)));
+        assert!(!TokensByOwner::<Test>::contains_key((LEARNER, class_id, token_id)));
+    });
+}
+
+#[test]
+fn transfers_and_delisting_cancel_the_sale() {
+    new_test_ext().execute_with(|| {
+        let (class_id, token_id) = transferable_token(LEARNER);
+        assert_ok!(CoursesModule::list_token(Origin::signed(LEARNER), class_id, token_id, 100));
+        assert_ok!(CoursesModule::delist_token(Origin::signed(LEARNER), class_id, token_id));
+        assert_noop!(
+            CoursesModule::buy_token(Origin::signed(REPORTER), class_id, token_id, 100),
+            Error::<Test>::TokenNotForSale
+        );
+
+        assert_ok!(CoursesModule::list_token(Origin::signed(LEARNER), class_id, token_id, 100));
+        assert_noop!(
+            CoursesModule::transfer_token(Origin::signed(LEARNER), class_id, token_id, LEARNER),
+            Error::<Test>::TransferToSelf
+        );
+        assert_ok!(CoursesModule::transfer_token(Origin::signed(LEARNER), class_id, token_id, FUNDER));
+        assert_eq!(CoursesModule::tokens(class_id, token_id).unwrap().owner, FUNDER);
+        assert!(CoursesModule::listings(class_id, token_id).is_none());
+    });
+}
+
+#[test]
+fn bound_tokens_cannot_be_listed_or_transferred() {
+    new_test_ext().execute_with(|| {
+        let class_id = CoursesModule::create_class(&OWNER, TokenType::BoundToAddress).unwrap();
+        let token_id = CoursesModule::mint_token(class_id, &LEARNER).unwrap();
+        assert_noop!(
+            CoursesModule::list_token(Origin::signed(LEARNER), class_id, token_id, 100),
+            Error::<Test>::TokenNotTransferable
+        );
+        assert_noop!(
+            CoursesModule::transfer_token(Origin::signed(LEARNER), class_id, token_id, REPORTER),
+            Error::<Test>::TokenNotTransferable
+        );
+    });
+}
+
+#[test]
+fn nonfungible_classes_keep_clear_of_assigned_ids() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(
+            <CoursesModule as Create<u64>>::create_class(&5, &OWNER, &LEARNER),
+            Error::<Test>::ClassAdminNotOwner
+        );
+        assert_ok!(<CoursesModule as Create<u64>>::create_class(&5, &OWNER, &OWNER));
+        assert_noop!(
+            <CoursesModule as Create<u64>>::create_class(&5, &OWNER, &OWNER),
+            Error::<Test>::ClassAlreadyExists
+        );
+        assert_eq!(<CoursesModule as Inspect<u64>>::class_owner(&5), Some(OWNER));
+        assert_eq!(CoursesModule::create_class(&OWNER, TokenType::Transferable), Ok(6));
+
+        assert_ok!(<CoursesModule as Mutate<u64>>::mint_into(&5, &3, &LEARNER));
+        assert_noop!(
+            <CoursesModule as Mutate<u64>>::mint_into(&5, &3, &REPORTER),
+            Error::<Test>::TokenAlreadyExists
+        );
+        assert_eq!(CoursesModule::mint_token(5, &LEARNER), Ok(4));
+        assert_eq!(CoursesModule::classes(5).unwrap().total_issuance, 2);
+    });
+}
+
+#[test]
+fn nonfungible_tokens_follow_the_class_token_type() {
+    new_test_ext().execute_with(|| {
+        let (class_id, token_id) = transferable_token(LEARNER);
+        assert_eq!(<CoursesModule as Inspect<u64>>::owner(&class_id, &token_id), Some(LEARNER));
+        assert!(<CoursesModule as Inspect<u64>>::can_transfer(&class_id, &token_id));
+        assert_eq!(
+            <CoursesModule as InspectEnumerable<u64>>::owned_in_class(&class_id, &LEARNER).collect::<Vec<_>>(),
+            vec![token_id]
+        );
+
+        assert_ok!(CoursesModule::list_token(Origin::signed(LEARNER), class_id, token_id, 100));
+        assert_ok!(<CoursesModule as Transfer<u64>>::transfer(&class_id, &token_id, &REPORTER));
+        assert_eq!(<CoursesModule as Inspect<u64>>::owner(&class_id, &token_id), Some(REPORTER));
+        assert!(CoursesModule::listings(class_id, token_id).is_none());
+        assert_eq!(<CoursesModule as InspectEnumerable<u64>>::owned(&LEARNER).count(), 0);
+
+        assert_ok!(CoursesModule::list_token(Origin::signed(REPORTER), class_id, token_id, 100));
+        assert_ok!(<CoursesModule as Mutate<u64>>::burn_from(&class_id, &token_id));
+        assert_eq!(<CoursesModule as Inspect<u64>>::owner(&class_id, &token_id), None);
+        assert!(CoursesModule::listings(class_id, token_id).is_none());
+        assert_eq!(<CoursesModule as InspectEnumerable<u64>>::owned(&REPORTER).count(), 0);
+        assert_eq!(CoursesModule::classes(class_id).unwrap().total_issuance, 0);
+
+        let bound_class = CoursesModule::create_class(&OWNER, TokenType::BoundToAddress).unwrap();
+        let bound_token = CoursesModule::mint_token(bound_class, &LEARNER).unwrap();
+        assert!(!<CoursesModule as Inspect<u64>>::can_transfer(&bound_class, &bound_token));
+        assert_noop!(
+            <CoursesModule as Transfer<u64>>::transfer(&bound_class, &bound_token, &REPORTER),
+            Error::<Test>::TokenNotTransferable
+        );
+        assert_noop!(
+            <CoursesModule as Mutate<u64>>::burn_from(&bound_class, &bound_token),
+            Error::<Test>::TokenNotTransferable
+        );
+    });
+}
+
+#[test]
+fn badge_rules_are_defined_by_the_course_owner_or_governance() {
+    new_test_ext().execute_with(|| {
+        let (course_id, _) = published_course(OWNER, 1, None);
+        let rule = || BadgeCondition::LecturesCompleted(1);
+        assert_noop!(
+            CoursesModule::create_badge_rule(Origin::signed(LEARNER), Some(course_id), b"Badge".to_vec(), rule()),
+            Error::<Test>::NotCourseOwner
+        );
+        assert_noop!(
+            CoursesModule::create_badge_rule(Origin::signed(OWNER), None, b"Badge".to_vec(), rule()),
+            DispatchError::BadOrigin
+        );
+        assert_ok!(CoursesModule::create_badge_rule(Origin::root(), None, b"Badge".to_vec(), rule()));
+        assert_ok!(CoursesModule::create_badge_rule(Origin::root(), Some(course_id), b"Badge".to_vec(), rule()));
+        for _ in 1..8 {
+            assert_ok!(CoursesModule::create_badge_rule(
+                Origin::signed(OWNER),
+                Some(course_id),
+                b"Badge".to_vec(),
+                rule()
+            ));
+        }
+        assert_noop!(
+            CoursesModule::create_badge_rule(Origin::signed(OWNER), Some(course_id), b"Badge".to_vec(), rule()),
+            Error::<Test>::TooManyBadgeRules
+        );
+        // Badges are bound to the learner earning them.
+        let class_id = CoursesModule::badge_rules(1).unwrap().class_id;
+        assert_eq!(CoursesModule::classes(class_id).unwrap().token_type, TokenType::BoundToAddress);
+
+        assert_noop!(
+            CoursesModule::remove_badge_rule(Origin::signed(LEARNER), 1),
+            Error::<Test>::NotCourseOwner
+        );
+        assert_noop!(CoursesModule::remove_badge_rule(Origin::signed(OWNER), 0), DispatchError::BadOrigin);
+        assert_noop!(
+            CoursesModule::remove_badge_rule(Origin::signed(OWNER), 9),
+            Error::<Test>::BadgeRuleNotExist
+        );
+        assert_ok!(CoursesModule::remove_badge_rule(Origin::signed(OWNER), 1));
+        assert_ok!(CoursesModule::create_badge_rule(Origin::signed(OWNER), Some(course_id), b"Badge".to_vec(), rule()));
+    });
+}
+
+#[test]
+fn lecture_badges_count_the_course_or_every_lecture() {
+    new_test_ext().execute_with(|| {
+        let (course_a, lectures_a) = published_course(OWNER, 3, None);
+        let (course_b, lectures_b) = published_course(OWNER, 2, None);
+        assert_ok!(CoursesModule::create_badge_rule(
+            Origin::signed(OWNER),
+            Some(course_a),
+            b"Two lectures".to_vec(),
+            BadgeCondition::LecturesCompleted(2)
+        ));
+        assert_ok!(CoursesModule::create_badge_rule(
+            Origin::root(),
+            None,
+            b"Three lectures".to_vec(),
+            BadgeCondition::LecturesCompleted(3)
+        ));
+        assert_ok!(CoursesModule::create_badge_rule(
+            Origin::signed(OWNER),
+            Some(course_b),
+            b"Removed".to_vec(),
+            BadgeCondition::LecturesCompleted(1)
+        ));
+        assert_ok!(CoursesModule::remove_badge_rule(Origin::signed(OWNER), 2));
+
+        assert_ok!(CoursesModule::complete_lecture(Origin::signed(LEARNER), course_a, lectures_a[0]));
+        complete_all(LEARNER, course_b, &lectures_b);
+        // Lectures of other courses count towards global rules only.
+        assert_eq!(CoursesModule::badges_awarded(LEARNER, 0), None);
+        assert_eq!(CoursesModule::badges_awarded(LEARNER, 1), Some(0));
+        assert_eq!(CoursesModule::badges_awarded(LEARNER, 2), None);
+
+        assert_ok!(CoursesModule::complete_lecture(Origin::signed(LEARNER), course_a, lectures_a[1]));
+        let class_id = CoursesModule::badge_rules(0).unwrap().class_id;
+        System::assert_last_event(Event::CoursesModule(crate::Event::BadgeAwarded(LEARNER, 0, class_id, 0)));
+        assert_eq!(CoursesModule::badges_awarded(LEARNER, 0), Some(0));
+        assert_eq!(CoursesModule::tokens(class_id, 0).unwrap().owner, LEARNER);
+
+        // Badges are only minted once per rule.
+        assert_ok!(CoursesModule::complete_lecture(Origin::signed(LEARNER), course_a, lectures_a[2]));
+        assert_eq!(CoursesModule::classes(class_id).unwrap().total_issuance, 1);
+    });
+}
+
+#[test]
+fn course_badges_check_the_category_and_completion_time() {
+    new_test_ext().execute_with(|| {
+        let (course_a, lectures_a) = published_course(OWNER, 2, None);
+        let (course_b, lectures_b) = published_course(OWNER, 1, None);
+        assert_ok!(CoursesModule::create_badge_rule(
+            Origin::root(),
+            None,
+            b"Programmer".to_vec(),
+            BadgeCondition::CategoryCourses { category: b"programming".to_vec(), count: 2 }
+        ));
+        assert_ok!(CoursesModule::create_badge_rule(
+            Origin::root(),
+            None,
+            b"Fast learner".to_vec(),
+            BadgeCondition::CourseWithin(1_000)
+        ));
+
+        Timestamp::set_timestamp(1_000);
+        assert_ok!(CoursesModule::complete_lecture(Origin::signed(LEARNER), course_a, lectures_a[0]));
+        assert_ok!(CoursesModule::complete_lecture(Origin::signed(REPORTER), course_a, lectures_a[0]));
+        Timestamp::set_timestamp(1_500);
+        assert_ok!(CoursesModule::complete_lecture(Origin::signed(LEARNER), course_a, lectures_a[1]));
+        assert!(CoursesModule::badges_awarded(LEARNER, 1).is_some());
+        assert_eq!(CoursesModule::badges_awarded(LEARNER, 0), None);
+
+        Timestamp::set_timestamp(3_000);
+        assert_ok!(CoursesModule::complete_lecture(Origin::signed(REPORTER), course_a, lectures_a[1]));
+        assert_eq!(CoursesModule::badges_awarded(REPORTER, 1), None);
+
+        complete_all(LEARNER, course_b, &lectures_b);
+        assert!(CoursesModule::badges_awarded(LEARNER, 0).is_some());
+        assert_eq!(CoursesModule::category_completions(LEARNER, BlakeTwo256::hash_of(&b"programming".to_vec())), 2);
+    });
+}
+
+#[test]
+fn streaks_count_consecutive_days_of_study() {
+    new_test_ext().execute_with(|| {
+        let (course_id, lectures) = published_course(OWNER, 4, None);
+        assert_ok!(CoursesModule::create_badge_rule(
+            Origin::root(),
+            None,
+            b"Two days".to_vec(),
+            BadgeCondition::Streak(2)
+        ));
+
+        Timestamp::set_timestamp(500);
+        assert_ok!(CoursesModule::complete_lecture(Origin::signed(LEARNER), course_id, lectures[0]));
+        assert_ok!(CoursesModule::complete_lecture(Origin::signed(LEARNER), course_id, lectures[1]));
+        assert_eq!(CoursesModule::learner_streak(&LEARNER), (1, 1));
+        assert_eq!(CoursesModule::badges_awarded(LEARNER, 0), None);
+
+        Timestamp::set_timestamp(1_500);
+        assert_ok!(CoursesModule::complete_lecture(Origin::signed(LEARNER), course_id, lectures[2]));
+        assert_eq!(CoursesModule::learner_streak(&LEARNER), (2, 2));
+        assert!(CoursesModule::badges_awarded(LEARNER, 0).is_some());
+
+        // The streak is kept until a full day passes without activity.
+        Timestamp::set_timestamp(2_999);
+        assert_eq!(CoursesModule::learner_streak(&LEARNER), (2, 2));
+        Timestamp::set_timestamp(3_000);
+        assert_eq!(CoursesModule::learner_streak(&LEARNER), (0, 2));
+
+        assert_ok!(CoursesModule::complete_lecture(Origin::signed(LEARNER), course_id, lectures[3]));
+        assert_eq!(CoursesModule::streaks(LEARNER), Streak { last_day: 3, current: 1 });
+        assert_eq!(CoursesModule::learner_streak(&LEARNER), (1, 2));
+    });
+}
+
+#[test]
+fn speed_leaderboard_ranks_the_fastest_completions() {
+    new_test_ext().execute_with(|| {
+        let (course_id, lectures) = published_course(OWNER, 2, None);
+        let learners = [LEARNER, REPORTER, FUNDER, 5];
+        Timestamp::set_timestamp(1_000);
+        for learner in learners.iter() {
+            assert_ok!(CoursesModule::complete_lecture(Origin::signed(*learner), course_id, lectures[0]));
+        }
+        for (learner, time) in [(5, 1_100), (REPORTER, 1_200), (LEARNER, 1_400), (FUNDER, 1_600)].iter() {
+            Timestamp::set_timestamp(*time);
+            assert_ok!(CoursesModule::complete_lecture(Origin::signed(*learner), course_id, lectures[1]));
+        }
+
+        // The board keeps the `MaxLeaderboardSize` fastest learners.
+        assert_eq!(
+            CoursesModule::leaderboard(&course_id, LeaderboardKind::Speed),
+            vec![(5, 100), (REPORTER, 200), (LEARNER, 400)]
+        );
+        assert!(CoursesModule::leaderboard(&course_id, LeaderboardKind::Score).is_empty());
+    });
+}
+
+#[test]
+fn score_leaderboard_ranks_the_total_assignment_scores() {
+    new_test_ext().execute_with(|| {
+        let course_id = create_course(OWNER);
+        let first = create_assignment(OWNER, course_id, 1);
+        let second = create_assignment(OWNER, course_id, 1);
+        assert_ok!(CoursesModule::publish_course(Origin::signed(OWNER), course_id));
+        fill_reviewer_pool(OWNER, course_id, &[5]);
+
+        for (learner, score) in [(LEARNER, 70), (REPORTER, 90), (FUNDER, 60), (OWNER, 40)].iter() {
+            assert_ok!(CoursesModule::submit_assignment(Origin::signed(*learner), course_id, first, H256::repeat_byte(1)));
+            assert_ok!(CoursesModule::review_assignment(Origin::signed(5), course_id, first, *learner, *score));
+        }
+        // Failed assignments are not ranked.
+        assert_eq!(
+            CoursesModule::leaderboard(&course_id, LeaderboardKind::Score),
+            vec![(REPORTER, 90), (LEARNER, 70), (FUNDER, 60)]
+        );
+
+        assert_ok!(CoursesModule::submit_assignment(Origin::signed(LEARNER), course_id, second, H256::repeat_byte(2)));
+        assert_ok!(CoursesModule::review_assignment(Origin::signed(5), course_id, second, LEARNER, 80));
+        assert_eq!(
+            CoursesModule::leaderboard(&course_id, LeaderboardKind::Score),
+            vec![(LEARNER, 150), (REPORTER, 90), (FUNDER, 60)]
+        );
+    });
+}
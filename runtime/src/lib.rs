@@ -34,7 +34,7 @@ pub use frame_support::{
         constants::{BlockExecutionWeight, ExtrinsicBaseWeight, RocksDbWeight, WEIGHT_PER_SECOND},
         IdentityFee, Weight,
     },
-    Identity, PalletId,
+    PalletId,
     StorageValue,
 };
 pub use pallet_balances::Call as BalancesCall;
@@ -46,6 +46,7 @@ pub use sp_runtime::{Perbill, Permill};
 
 /// Import the template pallet.
 pub use pallet_kitties;
+pub use pallet_courses;
 
 /// An index to a block.
 pub type BlockNumber = u32;
@@ -102,7 +103,7 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
     //   `spec_version`, and `authoring_version` are the same between Wasm and native.
     // This value is set to 100 to notify Polkadot-JS App (https://polkadot.js.org/apps) to use
     //   the compatible custom types.
-    spec_version: 104,
+    spec_version: 127,
     impl_version: 1,
     apis: RUNTIME_API_VERSIONS,
     transaction_version: 1,
@@ -380,6 +381,74 @@ impl pallet_treasury::Config for Runtime {
     type SpendFunds = ();
 }
 
+parameter_types! {
+	pub const AssetDeposit: Balance = 100 * currency::DOLLARS;
+	pub const ApprovalDeposit: Balance = 1 * currency::DOLLARS;
+	pub const AssetsStringLimit: u32 = 50;
+	pub const MetadataDepositBase: Balance = 10 * currency::DOLLARS;
+	pub const MetadataDepositPerByte: Balance = 1 * currency::DOLLARS;
+}
+
+impl pallet_assets::Config for Runtime {
+    type Event = Event;
+    type Balance = Balance;
+    type AssetId = u32;
+    type Currency = Balances;
+    type ForceOrigin = EnsureRoot<AccountId>;
+    type AssetDeposit = AssetDeposit;
+    type MetadataDepositBase = MetadataDepositBase;
+    type MetadataDepositPerByte = MetadataDepositPerByte;
+    type ApprovalDeposit = ApprovalDeposit;
+    type StringLimit = AssetsStringLimit;
+    type Freezer = ();
+    type Extra = ();
+    type WeightInfo = pallet_assets::weights::SubstrateWeight<Runtime>;
+}
+
+parameter_types! {
+	pub const BasicDeposit: Balance = 10 * currency::DOLLARS;
+	pub const FieldDeposit: Balance = 250 * currency::CENTS;
+	pub const SubAccountDeposit: Balance = 2 * currency::DOLLARS;
+	pub const MaxSubAccounts: u32 = 100;
+	pub const MaxAdditionalFields: u32 = 100;
+	pub const MaxRegistrars: u32 = 20;
+}
+
+// Simple majority of the council (or root) can add registrars and kill identities
+type IdentityOrigin = EnsureOneOf<
+    AccountId,
+    EnsureRoot<AccountId>,
+    pallet_collective::EnsureProportionMoreThan<_1, _2, AccountId, CouncilInstance>,
+>;
+
+impl pallet_identity::Config for Runtime {
+    type Event = Event;
+    type Currency = Balances;
+    type BasicDeposit = BasicDeposit;
+    type FieldDeposit = FieldDeposit;
+    type SubAccountDeposit = SubAccountDeposit;
+    type MaxSubAccounts = MaxSubAccounts;
+    type MaxAdditionalFields = MaxAdditionalFields;
+    type MaxRegistrars = MaxRegistrars;
+    // Deposits of killed identities go to the treasury
+    type Slashed = Treasury;
+    type ForceOrigin = IdentityOrigin;
+    type RegistrarOrigin = IdentityOrigin;
+    type WeightInfo = pallet_identity::weights::SubstrateWeight<Runtime>;
+}
+
+/// Instructors are verified once a registrar judged their identity reasonable or known good.
+pub struct IdentityInstructorVerifier;
+impl pallet_courses::InstructorVerifier<AccountId> for IdentityInstructorVerifier {
+    fn is_verified(who: &AccountId) -> bool {
+        Identity::identity(who).map_or(false, |registration| {
+            registration.judgements.iter().any(|(_, judgement)| {
+                matches!(judgement, pallet_identity::Judgement::KnownGood | pallet_identity::Judgement::Reasonable)
+            })
+        })
+    }
+}
+
 // parameter_types! {
 // 	pub const UncleGenerations: u32 = 0;
 // }
@@ -475,16 +544,93 @@ parameter_types! {
   pub const MaxCourseOwned: u32 = 9999;
   pub const MaxClassMetadata: u32 = 1024;
   pub const MaxTokenMetadata: u32 = 1024;
+  pub const MaxClassRoyalty: Perbill = Perbill::from_percent(25);
+  pub const CoursesPalletId: PalletId = PalletId(*b"py/crses");
+  pub const CourseBond: Balance = 10 * currency::DOLLARS;
+  pub const ComplaintDeposit: Balance = 1 * currency::DOLLARS;
+  pub const MaxComplaints: u32 = 32;
+  pub const ComplaintPeriod: BlockNumber = 30 * DAYS;
+  pub const MaxReviewers: u32 = 5;
+  pub const MaxReviewerPool: u32 = 256;
+  pub const PassingScore: u8 = 60;
+  pub const ReviewTolerance: u8 = 10;
+  pub const ReviewPeriod: BlockNumber = 7 * DAYS;
+  pub const MaxPathCourses: u32 = 32;
+  pub const MaxLearnerPaths: u32 = 64;
+  pub const MaxCouponRedemptions: u32 = 1024;
+  pub const MaxPrerequisites: u32 = 16;
+  pub const MaxEditionLectures: u32 = 256;
+  pub const MaxWrappedKeyLen: u32 = 128;
+  pub const MaxBulkLectures: u32 = 64;
+  pub const MaxLanguageTagLen: u32 = 16;
+  pub const MaxTranslations: u32 = 64;
+  pub const MaxBadgeRules: u32 = 64;
+  // Timestamps are in milliseconds
+  pub const DayLength: u64 = 24 * 60 * 60 * 1000;
+  pub const MaxLeaderboardSize: u32 = 10;
+  pub const RequireVerifiedInstructor: bool = true;
 }
 
+// Simple majority of the council (or root) can manage course reward plans
+type CourseRewardOrigin = EnsureOneOf<
+    AccountId,
+    EnsureRoot<AccountId>,
+    pallet_collective::EnsureProportionMoreThan<_1, _2, AccountId, CouncilInstance>,
+>;
+
+// Simple majority of either the council or the technical committee (or root) can take courses down
+type CourseTakedownOrigin = EnsureOneOf<
+    AccountId,
+    EnsureRoot<AccountId>,
+    EnsureOneOf<
+        AccountId,
+        pallet_collective::EnsureProportionMoreThan<_1, _2, AccountId, CouncilInstance>,
+        pallet_collective::EnsureProportionMoreThan<_1, _2, AccountId, TechCommitteeInstance>,
+    >,
+>;
+
 /// Configure the pallet-kitties in pallets/kitties.
 impl pallet_courses::Config for Runtime {
     type Event = Event;
     type Currency = Balances;
     type CourseRandomness = RandomnessCollectiveFlip;
+    type PalletId = CoursesPalletId;
+    type RewardOrigin = CourseRewardOrigin;
+    type CourseBond = CourseBond;
+    type TakedownOrigin = CourseTakedownOrigin;
+    type ComplaintDeposit = ComplaintDeposit;
+    type MaxComplaints = MaxComplaints;
+    type ComplaintPeriod = ComplaintPeriod;
+    // Slashed instructor bonds and complaint deposits go to the treasury
+    type Slashed = Treasury;
+    type Time = Timestamp;
+    type MaxReviewers = MaxReviewers;
+    type MaxReviewerPool = MaxReviewerPool;
+    type PassingScore = PassingScore;
+    type ReviewTolerance = ReviewTolerance;
+    type ReviewPeriod = ReviewPeriod;
+    type MaxPathCourses = MaxPathCourses;
+    type MaxLearnerPaths = MaxLearnerPaths;
+    type MaxCouponRedemptions = MaxCouponRedemptions;
+    type MaxPrerequisites = MaxPrerequisites;
+    type MaxEditionLectures = MaxEditionLectures;
+    type MaxWrappedKeyLen = MaxWrappedKeyLen;
+    type MaxBulkLectures = MaxBulkLectures;
+    type MaxLanguageTagLen = MaxLanguageTagLen;
+    type MaxTranslations = MaxTranslations;
+    type MaxBadgeRules = MaxBadgeRules;
+    type DayLength = DayLength;
+    type MaxLeaderboardSize = MaxLeaderboardSize;
+    // Only instructors with an identity judged by a registrar can publish courses
+    type InstructorVerifier = IdentityInstructorVerifier;
+    type RequireVerifiedInstructor = RequireVerifiedInstructor;
+    // Courses can be priced in the assets of pallet-assets
+    type AssetId = u32;
+    type Assets = Assets;
     type MaxCourseOwned = MaxCourseOwned;
     type MaxClassMetadata = MaxClassMetadata;
     type MaxTokenMetadata = MaxTokenMetadata;
+    type MaxClassRoyalty = MaxClassRoyalty;
 }
 
 
@@ -509,6 +655,8 @@ construct_runtime!(
 		TechnicalCommittee: pallet_collective::<Instance2>::{Pallet, Call, Storage, Origin<T>, Event<T>, Config<T>} = 21,
 
 		Treasury: pallet_treasury::{Pallet, Storage, Config, Event<T>, Call} = 22,
+		Assets: pallet_assets::{Pallet, Call, Storage, Event<T>} = 23,
+		Identity: pallet_identity::{Pallet, Call, Storage, Event<T>} = 24,
 
 		// Include the custom logic from the pallet-kitties in the runtime.
 		SubstrateKitties: pallet_kitties::{Pallet, Call, Config<T>, Storage, Event<T>} = 30,
@@ -674,6 +822,62 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl pallet_courses_runtime_api::CoursesApi<
+		Block,
+		AccountId,
+		Hash,
+		pallet_courses::CourseCompletion<Runtime>,
+		pallet_courses::Lecture<Runtime>,
+		pallet_courses::CourseAnalytics<Hash, u32, Balance>,
+		pallet_courses::LocalizedText,
+	> for Runtime {
+		fn course_completion(
+			learner: AccountId,
+			course_id: Hash,
+		) -> Option<pallet_courses::CourseCompletion<Runtime>> {
+			Courses::course_completion(&learner, &course_id)
+		}
+
+		fn lectures(
+			course_id: Hash,
+			learner: Option<AccountId>,
+		) -> Vec<(Hash, pallet_courses::Lecture<Runtime>)> {
+			Courses::released_lectures(&course_id, learner)
+		}
+
+		fn is_verified_instructor(account: AccountId) -> bool {
+			Courses::is_verified_instructor(&account)
+		}
+
+		fn course_analytics(course_id: Hash) -> pallet_courses::CourseAnalytics<Hash, u32, Balance> {
+			Courses::course_analytics(&course_id)
+		}
+
+		fn localized_course(course_id: Hash, language: Vec<u8>) -> Option<pallet_courses::LocalizedText> {
+			Courses::localized_course(&course_id, language)
+		}
+
+		fn localized_lectures(
+			course_id: Hash,
+			learner: Option<AccountId>,
+			language: Vec<u8>,
+		) -> Vec<(Hash, pallet_courses::LocalizedText)> {
+			Courses::localized_lectures(&course_id, learner, language)
+		}
+
+		fn streak(learner: AccountId) -> (u32, u32) {
+			Courses::learner_streak(&learner)
+		}
+
+		fn speed_leaderboard(course_id: Hash) -> Vec<(AccountId, u64)> {
+			Courses::leaderboard(&course_id, pallet_courses::LeaderboardKind::Speed)
+		}
+
+		fn score_leaderboard(course_id: Hash) -> Vec<(AccountId, u64)> {
+			Courses::leaderboard(&course_id, pallet_courses::LeaderboardKind::Score)
+		}
+	}
+
 	#[cfg(feature = "runtime-benchmarks")]
 	impl frame_benchmarking::Benchmark<Block> for Runtime {
 		fn benchmark_metadata(extra: bool) -> (